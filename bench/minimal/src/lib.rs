@@ -0,0 +1,41 @@
+#![allow(unexpected_cfgs)]
+//! Minimal program linking `solana-pubkey-compare` with default features off
+//!
+//! The program checks that its first account is owned by itself, with
+//! [`fast_eq`] or, under the `reference` feature, with `==`. The two builds
+//! differ only in that comparison, so the difference in their sizes is what
+//! the minimal configuration adds to a program.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path bench/minimal/Cargo.toml
+//! cargo build-sbf --manifest-path bench/minimal/Cargo.toml --features reference \
+//!     --sbf-out-dir bench/minimal/target/deploy/reference
+//! ```
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    let account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    #[cfg(not(feature = "reference"))]
+    let owned = solana_pubkey_compare::fast_eq(account.owner, program_id);
+    #[cfg(feature = "reference")]
+    let owned = account.owner == program_id;
+
+    if owned {
+        Ok(())
+    } else {
+        Err(ProgramError::IllegalOwner)
+    }
+}
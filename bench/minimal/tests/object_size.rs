@@ -0,0 +1,40 @@
+//! Guards the object size of the minimal (`default-features = false`) build
+//!
+//! Compares the deployed `.so` of this program against its `reference`
+//! build, so the entrypoint and `solana-program` code both share cancels out
+//! and what remains is the assembly, wrapper and symbols the crate links.
+#![cfg(feature = "object-size")]
+
+use std::path::{Path, PathBuf};
+
+/// Bytes the minimal configuration may add to a program over `==`: the
+/// 144-byte `fast_eq` routine plus its call, relocation and symbol
+const MINIMAL_OBJECT_BUDGET: u64 = 256;
+
+fn deploy_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/deploy")
+}
+
+fn program_size(dir: &Path, build: &str) -> u64 {
+    let program_so = dir.join("solana_pubkey_compare_minimal.so");
+    let metadata = std::fs::metadata(&program_so).unwrap_or_else(|err| {
+        panic!(
+            "{}: {err}; build the {build} program first, see src/lib.rs",
+            program_so.display()
+        )
+    });
+    metadata.len()
+}
+
+#[test]
+fn minimal_configuration_fits_object_budget() {
+    let minimal = program_size(&deploy_dir(), "minimal");
+    let reference = program_size(&deploy_dir().join("reference"), "reference");
+    let added = minimal.saturating_sub(reference);
+
+    assert!(
+        added <= MINIMAL_OBJECT_BUDGET,
+        "minimal configuration adds {added} bytes ({minimal} vs {reference}), \
+         over {MINIMAL_OBJECT_BUDGET}"
+    );
+}
@@ -0,0 +1,76 @@
+//! Runs bench program instructions under mollusk and reads their cost
+//!
+//! The bench program must already be built with
+//! `cargo build-sbf --manifest-path bench/Cargo.toml`.
+
+use std::path::PathBuf;
+
+use mollusk_svm::{result::Check, Mollusk};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+/// Bench opcodes, as defined in `bench/src/lib.rs`
+pub const FAST_EQ: u8 = 1;
+pub const FAST_EQ_BRANCHLESS: u8 = 2;
+pub const BASELINE: u8 = 5;
+
+/// The bench program loaded into a mollusk instance
+pub struct Bench {
+    mollusk: Mollusk,
+    program_id: Pubkey,
+    baseline: u64,
+}
+
+impl Bench {
+    /// Loads the bench program from `target/deploy`
+    pub fn load() -> Self {
+        let deploy_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../target/deploy");
+        let program_so = deploy_dir.join("solana_pubkey_compare_bench.so");
+        assert!(
+            program_so.exists(),
+            "{} not found; run `cargo build-sbf --manifest-path bench/Cargo.toml` first",
+            program_so.display()
+        );
+        std::env::set_var("SBF_OUT_DIR", &deploy_dir);
+
+        let program_id = Pubkey::new_unique();
+        let mollusk = Mollusk::new(&program_id, "solana_pubkey_compare_bench");
+        let mut bench = Bench {
+            mollusk,
+            program_id,
+            baseline: 0,
+        };
+        bench.baseline = bench.consumed(BASELINE, &[0; 32], &[0; 32]);
+        bench
+    }
+
+    /// Returns the cost of running `opcode` over `lhs` and `rhs`, less the
+    /// cost of the `Baseline` opcode
+    pub fn cost(&self, opcode: u8, lhs: &[u8; 32], rhs: &[u8; 32]) -> u64 {
+        self.consumed(opcode, lhs, rhs) - self.baseline
+    }
+
+    fn consumed(&self, opcode: u8, lhs: &[u8; 32], rhs: &[u8; 32]) -> u64 {
+        let mut data = Vec::with_capacity(65);
+        data.push(opcode);
+        data.extend_from_slice(lhs);
+        data.extend_from_slice(rhs);
+        let instruction = Instruction::new_with_bytes(self.program_id, &data, Vec::new());
+        self.mollusk
+            .process_and_validate_instruction(&instruction, &[], &[Check::success()])
+            .compute_units_consumed
+    }
+}
+
+/// A key, followed by copies of it with one bit flipped in each byte
+/// position: equal keys, then a mismatch at every offset
+pub fn mismatch_inputs() -> ([u8; 32], Vec<[u8; 32]>) {
+    let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+    let inputs = std::iter::once(key)
+        .chain((0..32).map(|i| {
+            let mut other = key;
+            other[i] ^= 1;
+            other
+        }))
+        .collect();
+    (key, inputs)
+}
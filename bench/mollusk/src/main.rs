@@ -0,0 +1,47 @@
+//! Generates `costs_measured.rs` for the `measured-costs` feature
+//!
+//! Runs every bench routine under mollusk over inputs covering its best and
+//! worst cases, subtracts the cost of the `Baseline` opcode, and writes the
+//! resulting constants to the path given as the first argument (stdout if
+//! none). The bench program must already be built:
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path bench/Cargo.toml
+//! cargo run --manifest-path bench/mollusk/Cargo.toml -- costs_measured.rs
+//! ```
+
+use std::fmt::Write as _;
+
+use solana_pubkey_compare_mollusk::{mismatch_inputs, Bench, FAST_EQ, FAST_EQ_BRANCHLESS};
+
+/// Generated constants and the bench opcode measuring each
+const ROUTINES: &[(&str, u8)] = &[
+    ("FAST_EQ_EARLY_EXIT", FAST_EQ),
+    ("FAST_EQ_BRANCHLESS", FAST_EQ_BRANCHLESS),
+];
+
+fn main() {
+    let bench = Bench::load();
+    let (key, inputs) = mismatch_inputs();
+
+    let mut out = String::from(
+        "// @generated by bench/mollusk from a run of the bench program; do not edit\n",
+    );
+    for &(name, opcode) in ROUTINES {
+        let costs: Vec<u64> = inputs
+            .iter()
+            .map(|rhs| bench.cost(opcode, &key, rhs))
+            .collect();
+        let (best, worst) = (costs.iter().min().unwrap(), costs.iter().max().unwrap());
+        writeln!(
+            out,
+            "\npub const {name}: RoutineCost = RoutineCost {{ best: {best}, worst: {worst} }};"
+        )
+        .unwrap();
+    }
+
+    match std::env::args().nth(1) {
+        Some(path) => std::fs::write(&path, out).unwrap_or_else(|err| panic!("{path}: {err}")),
+        None => print!("{out}"),
+    }
+}
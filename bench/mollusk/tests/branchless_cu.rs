@@ -0,0 +1,42 @@
+//! Compares the branchless and early-exit equality routines in compute units
+//!
+//! sBPF charges one unit per executed instruction and nothing extra for a
+//! taken branch, so skipping the branches cannot pay for the extra XOR and OR
+//! instructions: the branchless routine costs the same for every input and
+//! never less than early exit, whose worst case is equal keys. This test
+//! pins both halves of that claim, and prints the costs it measured.
+
+use solana_pubkey_compare_mollusk::{mismatch_inputs, Bench, FAST_EQ, FAST_EQ_BRANCHLESS};
+
+#[test]
+fn branchless_is_constant_and_never_cheaper() {
+    let bench = Bench::load();
+    let (key, inputs) = mismatch_inputs();
+
+    println!("{:<24} {:>10} {:>10}", "keys", "early exit", "branchless");
+    let mut branchless_costs = Vec::new();
+    for (index, rhs) in inputs.iter().enumerate() {
+        let case = match index {
+            0 => "equal".to_string(),
+            _ => format!("byte {} differs", index - 1),
+        };
+        let early_exit = bench.cost(FAST_EQ, &key, rhs);
+        let branchless = bench.cost(FAST_EQ_BRANCHLESS, &key, rhs);
+        println!("{case:<24} {early_exit:>10} {branchless:>10}");
+
+        assert!(
+            early_exit <= branchless,
+            "{case}: branchless ({branchless} CU) beat early exit ({early_exit} CU); \
+             update the `fast_eq_branchless` docs"
+        );
+        branchless_costs.push(branchless);
+    }
+
+    assert!(
+        branchless_costs.windows(2).all(|pair| pair[0] == pair[1]),
+        "branchless cost depends on the input: {branchless_costs:?}"
+    );
+    // Equal keys are early exit's worst case, so this is the closest it gets
+    let equal = bench.cost(FAST_EQ, &key, &key);
+    println!("gap on equal keys: {} CU", branchless_costs[0] - equal);
+}
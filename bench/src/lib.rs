@@ -0,0 +1,112 @@
+#![allow(unexpected_cfgs)]
+//! Compute-unit benchmark program for `solana-pubkey-compare`
+//!
+//! Each instruction runs a single comparison routine between two keys taken
+//! from the instruction data, bracketed by `sol_log_compute_units` so the
+//! cost of the routine can be read straight from the transaction logs.
+//!
+//! ## Instruction Layout
+//!
+//! | Bytes  | Field                   |
+//! |--------|-------------------------|
+//! | 0      | Opcode (see [`Opcode`]) |
+//! | 1..33  | Left-hand key           |
+//! | 33..65 | Right-hand key          |
+//!
+//! ## Usage
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path bench/Cargo.toml
+//! ```
+//!
+//! Send the same key pair under several opcodes and diff the two logged
+//! compute-unit readings of each transaction to compare routines.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, log::sol_log_compute_units,
+    program_error::ProgramError, pubkey::Pubkey,
+};
+use solana_pubkey_compare::{fast_cmp, fast_eq, fast_eq_branchless, fast_eq_hint, fast_ne, Hint};
+
+/// Routine selected by the first instruction byte
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    /// Standard `PartialEq` on `Pubkey`, the baseline
+    PartialEq = 0,
+    /// [`fast_eq`] with the crate's default algorithm
+    FastEq = 1,
+    /// [`fast_eq_branchless`]
+    FastEqBranchless = 2,
+    /// [`fast_eq_hint`] with [`Hint::UsuallyEqual`]
+    HintUsuallyEqual = 3,
+    /// [`fast_eq_hint`] with [`Hint::UsuallyDifferent`]
+    HintUsuallyDifferent = 4,
+    /// Nothing between the two readings, to subtract measurement overhead
+    Baseline = 5,
+    /// [`fast_ne`]
+    FastNe = 6,
+    /// [`fast_cmp`]; the result is `true` if `lhs` orders first
+    FastCmp = 7,
+    /// [`fast_eq_hint`] with [`Hint::DiffersAtEnd`]
+    HintDiffersAtEnd = 8,
+    /// [`fast_eq_hint`] with [`Hint::ConstantCost`]
+    HintConstantCost = 9,
+}
+
+impl TryFrom<u8> for Opcode {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Opcode::PartialEq,
+            1 => Opcode::FastEq,
+            2 => Opcode::FastEqBranchless,
+            3 => Opcode::HintUsuallyEqual,
+            4 => Opcode::HintUsuallyDifferent,
+            5 => Opcode::Baseline,
+            6 => Opcode::FastNe,
+            7 => Opcode::FastCmp,
+            8 => Opcode::HintDiffersAtEnd,
+            9 => Opcode::HintConstantCost,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (&opcode, keys) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if keys.len() != 64 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let lhs = Pubkey::try_from(&keys[..32]).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let rhs = Pubkey::try_from(&keys[32..]).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let (lhs, rhs) = (core::hint::black_box(&lhs), core::hint::black_box(&rhs));
+
+    let opcode = Opcode::try_from(opcode)?;
+    sol_log_compute_units();
+    let result = match opcode {
+        Opcode::PartialEq => lhs == rhs,
+        Opcode::FastEq => fast_eq(lhs, rhs),
+        Opcode::FastEqBranchless => fast_eq_branchless(lhs, rhs),
+        Opcode::HintUsuallyEqual => fast_eq_hint(lhs, rhs, Hint::UsuallyEqual),
+        Opcode::HintUsuallyDifferent => fast_eq_hint(lhs, rhs, Hint::UsuallyDifferent),
+        Opcode::Baseline => false,
+        Opcode::FastNe => fast_ne(lhs, rhs),
+        Opcode::FastCmp => fast_cmp(lhs, rhs).is_lt(),
+        Opcode::HintDiffersAtEnd => fast_eq_hint(lhs, rhs, Hint::DiffersAtEnd),
+        Opcode::HintConstantCost => fast_eq_hint(lhs, rhs, Hint::ConstantCost),
+    };
+    sol_log_compute_units();
+
+    core::hint::black_box(result);
+    Ok(())
+}
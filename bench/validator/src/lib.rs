@@ -0,0 +1,225 @@
+//! End-to-end compute-unit measurement against `solana-test-validator`
+//!
+//! In-process harnesses such as `solana-program-test` and mollusk meter
+//! programs with their own copy of the runtime, and their accounting can
+//! drift from a real validator's. This harness boots a local validator with
+//! the bench program preloaded, sends one real transaction per routine, and
+//! reads the cost back over RPC: both from the two `sol_log_compute_units`
+//! readings the program logs and from the transaction's recorded
+//! `compute_units_consumed`. Its test compares those costs with the ones
+//! `bench/mollusk` measures for the same inputs.
+//!
+//! ## Usage
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path bench/Cargo.toml
+//! cargo test --manifest-path bench/validator/Cargo.toml --features test-validator -- --nocapture
+//! ```
+//!
+//! `BENCH_PROGRAM_SO` overrides the path of the program binary and
+//! `VALIDATOR_RPC_PORT` the RPC port (default 18899).
+
+use std::{
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::Transaction,
+};
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, UiTransactionEncoding,
+};
+
+/// How long to wait for the validator to answer RPC after spawning it
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Cost of one measured routine as seen by the validator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    /// Difference between the program's two `sol_log_compute_units`
+    /// readings, which includes the second syscall itself
+    pub logged_delta: u64,
+    /// `compute_units_consumed` recorded for the confirmed transaction
+    pub consumed: u64,
+}
+
+/// A running `solana-test-validator`, killed when dropped
+pub struct TestValidator {
+    process: Child,
+    ledger: PathBuf,
+    /// Client connected to the validator's RPC port
+    pub client: RpcClient,
+    /// Address the bench program is loaded at
+    pub program_id: Pubkey,
+    payer: Keypair,
+}
+
+impl TestValidator {
+    /// Starts a validator with the bench program at `program_so` preloaded,
+    /// and funds a fresh payer
+    pub fn start(program_so: &std::path::Path) -> Self {
+        let port: u16 = std::env::var("VALIDATOR_RPC_PORT")
+            .ok()
+            .and_then(|port| port.parse().ok())
+            .unwrap_or(18899);
+        let ledger =
+            std::env::temp_dir().join(format!("pubkey-compare-ledger-{}", std::process::id()));
+        let program_id = Pubkey::new_unique();
+        let process = Command::new("solana-test-validator")
+            .arg("--reset")
+            .arg("--quiet")
+            .arg("--ledger")
+            .arg(&ledger)
+            .args(["--rpc-port", &port.to_string()])
+            .args(["--faucet-port", &(port + 1001).to_string()])
+            .arg("--bpf-program")
+            .arg(program_id.to_string())
+            .arg(program_so)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("failed to spawn solana-test-validator; is it on PATH?");
+
+        let client = RpcClient::new_with_commitment(
+            format!("http://127.0.0.1:{port}"),
+            CommitmentConfig::confirmed(),
+        );
+        let mut validator = TestValidator {
+            process,
+            ledger,
+            client,
+            program_id,
+            payer: Keypair::new(),
+        };
+        validator.wait_until_ready();
+        validator.fund_payer();
+        validator
+    }
+
+    fn wait_until_ready(&mut self) {
+        let started = Instant::now();
+        while self.client.get_health().is_err() {
+            if let Some(status) = self.process.try_wait().expect("validator process") {
+                panic!("solana-test-validator exited early: {status}");
+            }
+            assert!(
+                started.elapsed() < STARTUP_TIMEOUT,
+                "validator did not start in time"
+            );
+            thread::sleep(Duration::from_millis(250));
+        }
+    }
+
+    fn fund_payer(&self) {
+        let signature = self
+            .client
+            .request_airdrop(&self.payer.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .expect("airdrop");
+        let started = Instant::now();
+        while !self.client.confirm_transaction(&signature).unwrap_or(false) {
+            assert!(
+                started.elapsed() < STARTUP_TIMEOUT,
+                "airdrop did not confirm"
+            );
+            thread::sleep(Duration::from_millis(250));
+        }
+    }
+
+    /// Sends one bench instruction running `opcode` over `lhs` and `rhs`,
+    /// returning its measured cost
+    pub fn measure(&self, opcode: u8, lhs: &[u8; 32], rhs: &[u8; 32]) -> Measurement {
+        let mut data = Vec::with_capacity(65);
+        data.push(opcode);
+        data.extend_from_slice(lhs);
+        data.extend_from_slice(rhs);
+        // An unused account makes every transaction unique, so repeated
+        // measurements are not rejected as already processed
+        let accounts = vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+        let instruction = Instruction::new_with_bytes(self.program_id, &data, accounts);
+        let blockhash = self.client.get_latest_blockhash().expect("blockhash");
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            blockhash,
+        );
+
+        let signature = self
+            .client
+            .send_and_confirm_transaction(&transaction)
+            .expect("send transaction");
+        let (logs, consumed) = self.fetch(&signature);
+        Measurement {
+            logged_delta: logged_delta(&logs),
+            consumed,
+        }
+    }
+
+    /// Returns the logs and consumed units of a confirmed transaction
+    fn fetch(&self, signature: &Signature) -> (Vec<String>, u64) {
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Json),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        };
+        let transaction = self
+            .client
+            .get_transaction_with_config(signature, config)
+            .expect("get transaction");
+        let meta = transaction.transaction.meta.expect("transaction meta");
+        let logs = match meta.log_messages {
+            OptionSerializer::Some(logs) => logs,
+            _ => panic!("transaction has no logs"),
+        };
+        let consumed = match meta.compute_units_consumed {
+            OptionSerializer::Some(units) => units,
+            _ => panic!("transaction has no compute_units_consumed"),
+        };
+        (logs, consumed)
+    }
+}
+
+impl Drop for TestValidator {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+        let _ = std::fs::remove_dir_all(&self.ledger);
+    }
+}
+
+/// Difference between the first two "consumption: N units remaining" logs
+fn logged_delta(logs: &[String]) -> u64 {
+    let remaining: Vec<u64> = logs
+        .iter()
+        .filter_map(|line| {
+            line.strip_prefix("Program consumption: ")?
+                .strip_suffix(" units remaining")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    match remaining[..] {
+        [before, after, ..] => before - after,
+        _ => panic!("expected two compute unit logs, got {logs:?}"),
+    }
+}
+
+/// Default location of the bench program built by `cargo build-sbf`
+pub fn default_program_so() -> PathBuf {
+    std::env::var_os("BENCH_PROGRAM_SO")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("../../target/deploy/solana_pubkey_compare_bench.so")
+        })
+}
@@ -0,0 +1,71 @@
+//! Measures every bench routine on a real validator
+//!
+//! Fails if a routine's cost on the validator differs from its cost under
+//! mollusk, or if it depends on anything but its inputs. Both sides are
+//! measured relative to the `Baseline` opcode, so the signature and account
+//! overhead of a real transaction, which mollusk does not model, cancels
+//! out.
+#![cfg(feature = "test-validator")]
+
+use solana_pubkey_compare_mollusk::{Bench, BASELINE};
+use solana_pubkey_compare_validator_cu::{default_program_so, TestValidator};
+
+/// Bench opcodes and their names, as defined in `bench/src/lib.rs`
+const OPCODES: &[(u8, &str)] = &[
+    (0, "PartialEq"),
+    (1, "fast_eq"),
+    (2, "fast_eq_branchless"),
+    (3, "fast_eq_hint(UsuallyEqual)"),
+    (4, "fast_eq_hint(UsuallyDifferent)"),
+    (6, "fast_ne"),
+    (7, "fast_cmp"),
+    (8, "fast_eq_hint(DiffersAtEnd)"),
+    (9, "fast_eq_hint(ConstantCost)"),
+];
+
+#[test]
+fn validator_metering_matches_mollusk() {
+    let program_so = default_program_so();
+    assert!(
+        program_so.exists(),
+        "{} not found; run `cargo build-sbf --manifest-path bench/Cargo.toml` first",
+        program_so.display()
+    );
+    let validator = TestValidator::start(&program_so);
+    let mollusk = Bench::load();
+
+    let key = [7u8; 32];
+    let mut first_differs = key;
+    first_differs[0] ^= 1;
+    let mut last_differs = key;
+    last_differs[31] ^= 1;
+    let cases = [
+        ("equal", key),
+        ("first byte differs", first_differs),
+        ("last byte differs", last_differs),
+    ];
+
+    let baseline = validator.measure(BASELINE, &key, &key);
+    println!(
+        "{:<32} {:<20} {:>7} {:>9} {:>8}",
+        "routine", "keys", "logged", "consumed", "mollusk"
+    );
+    for &(opcode, name) in OPCODES {
+        for (case, rhs) in &cases {
+            let run = validator.measure(opcode, &key, rhs);
+            let logged = run.logged_delta - baseline.logged_delta;
+            let consumed = run.consumed - baseline.consumed;
+            let expected = mollusk.cost(opcode, &key, rhs);
+            println!("{name:<32} {case:<20} {logged:>7} {consumed:>9} {expected:>8}");
+            assert_eq!(
+                consumed, expected,
+                "{name} ({case}): validator metering diverged from mollusk"
+            );
+            assert_eq!(
+                validator.measure(opcode, &key, rhs),
+                run,
+                "{name} ({case}): cost is not deterministic"
+            );
+        }
+    }
+}
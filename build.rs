@@ -1,16 +1,52 @@
-use std::env;
+use std::{env, path::Path};
+
+include!("src/asm/sources.rs");
+
+/// Environment variable naming the cost file generated by `bench/mollusk`,
+/// read with the `measured-costs` feature
+const MEASURED_COSTS_VAR: &str = "SOLANA_PUBKEY_COMPARE_COSTS";
+
+/// Copies the generated cost file into `OUT_DIR` for `src/costs.rs`
+fn copy_measured_costs() {
+    println!("cargo:rerun-if-env-changed={MEASURED_COSTS_VAR}");
+    let source = env::var(MEASURED_COSTS_VAR).unwrap_or_else(|_| {
+        panic!(
+            "the measured-costs feature needs {MEASURED_COSTS_VAR} set to a file \
+             generated by `cargo run --manifest-path bench/mollusk/Cargo.toml`"
+        )
+    });
+    println!("cargo:rerun-if-changed={source}");
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("costs_measured.rs");
+    std::fs::copy(&source, out).unwrap_or_else(|err| panic!("{source}: {err}"));
+}
+
+/// Returns `true` if the named cargo feature is enabled for this build
+fn feature_enabled(feature: &str) -> bool {
+    let var = format!("CARGO_FEATURE_{}", feature.to_uppercase().replace('-', "_"));
+    env::var_os(var).is_some()
+}
 
 fn main() {
     let target = env::var("TARGET").unwrap_or_default();
+    let opt_size = env::var_os("CARGO_FEATURE_OPT_SIZE").is_some();
 
     // Only compile and link assembly for BPF targets
     if target.contains("sbf") || target.contains("solana") {
-        cc::Build::new()
-            .file("src/asm/cmp_pubkey_eq.s")
-            .flag("-target")
-            .flag(&target)
-            .compile("cmp_pubkey_eq");
+        let mut build = cc::Build::new();
+        for (feature, source) in ASM_SOURCES {
+            if feature.is_none_or(feature_enabled) {
+                build.file(select_source(source, opt_size));
+            }
+        }
+        build.flag("-target").flag(&target).compile("cmp_pubkey_eq");
+    }
+
+    if feature_enabled("measured-costs") {
+        copy_measured_costs();
     }
 
-    println!("cargo:rerun-if-changed=src/asm/cmp_pubkey_eq.s");
+    for (_, source) in ASM_SOURCES {
+        println!("cargo:rerun-if-changed={source}");
+    }
+    println!("cargo:rerun-if-changed={OPT_SIZE_DIR}");
 }
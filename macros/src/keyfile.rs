@@ -0,0 +1,71 @@
+//! Key file formats accepted by `include_keys!`
+
+/// Decodes one base58 key
+pub fn decode(text: &str) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    match bs58::decode(text).onto(&mut key) {
+        Ok(32) => Ok(key),
+        Ok(len) => Err(format!("`{text}` decodes to {len} bytes, expected 32")),
+        Err(err) => Err(format!("`{text}` is not a base58 key: {err}")),
+    }
+}
+
+/// Parses a JSON array of base58 key strings
+///
+/// Only the subset of JSON needed for such an array is accepted: strings
+/// without escapes, commas and whitespace.
+pub fn parse_json(source: &str) -> Result<Vec<[u8; 32]>, String> {
+    let body = source
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or("expected a JSON array of base58 key strings")?;
+    if body.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    body.split(',')
+        .map(|item| {
+            let item = item.trim();
+            let text = item
+                .strip_prefix('"')
+                .and_then(|rest| rest.strip_suffix('"'))
+                .filter(|text| !text.contains(['"', '\\']))
+                .ok_or_else(|| format!("expected a base58 key string, found `{item}`"))?;
+            decode(text)
+        })
+        .collect()
+}
+
+/// Parses CSV with the base58 key in the first column
+///
+/// Blank lines and lines starting with `#` are skipped, as is the first row
+/// when it does not hold a key (a header).
+pub fn parse_csv(source: &str) -> Result<Vec<[u8; 32]>, String> {
+    let mut keys = Vec::new();
+    let rows = source
+        .lines()
+        .enumerate()
+        .map(|(line, row)| (line + 1, row.trim()))
+        .filter(|(_, row)| !row.is_empty() && !row.starts_with('#'));
+    for (index, (line, row)) in rows.enumerate() {
+        let field = row.split(',').next().unwrap().trim().trim_matches('"');
+        match decode(field) {
+            Ok(key) => keys.push(key),
+            Err(_) if index == 0 => continue,
+            Err(err) => return Err(format!("line {line}: {err}")),
+        }
+    }
+    Ok(keys)
+}
+
+/// Sorts keys byte-lexicographically, rejecting duplicates
+pub fn sorted_unique(mut keys: Vec<[u8; 32]>) -> Result<Vec<[u8; 32]>, String> {
+    keys.sort_unstable();
+    if let Some(pair) = keys.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(format!(
+            "duplicate key `{}`",
+            bs58::encode(pair[0]).into_string()
+        ));
+    }
+    Ok(keys)
+}
@@ -0,0 +1,191 @@
+//! Procedural macros for `solana-pubkey-compare`
+//!
+//! Re-exported by the main crate under its `macros` feature; depend on that
+//! rather than on this crate directly.
+
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{
+    bracketed,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Attribute, Ident, LitStr, Token, Visibility,
+};
+
+mod keyfile;
+mod tree;
+
+/// Builds a sorted `KeyTable` from a key file at compile time
+///
+/// The path is relative to the invoking crate's root (`CARGO_MANIFEST_DIR`).
+/// Files ending in `.json` must hold an array of base58 key strings; any
+/// other file is read as CSV with the base58 key in the first column, an
+/// optional header row, and `#` comments. Duplicate or malformed keys are
+/// compile errors, and the program is rebuilt when the file changes.
+///
+/// ```rust,ignore
+/// use solana_pubkey_compare::{KeyTable, include_keys};
+///
+/// static ALLOWLIST: KeyTable = include_keys!("allowlist.json");
+///
+/// if !ALLOWLIST.contains(signer.key) { ... }
+/// ```
+#[proc_macro]
+pub fn include_keys(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match read_key_file(&path).and_then(|(file, keys)| Ok((file, keyfile::sorted_unique(keys)?))) {
+        Ok((file, keys)) => {
+            let file = file.to_string_lossy();
+            let keys = keys.iter().map(|key| quote!([#(#key),*]));
+            quote! {{
+                const _: &[u8] = ::core::include_bytes!(#file);
+                ::solana_pubkey_compare::KeyTable::new(&[#(#keys),*])
+            }}
+            .into()
+        }
+        Err(message) => syn::Error::new(path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Reads and decodes the keys named by `path`, in file order
+fn read_key_file(path: &LitStr) -> Result<(PathBuf, Vec<[u8; 32]>), String> {
+    let root = std::env::var_os("CARGO_MANIFEST_DIR").ok_or("CARGO_MANIFEST_DIR is not set")?;
+    let file = PathBuf::from(root).join(path.value());
+    let source =
+        std::fs::read_to_string(&file).map_err(|err| format!("{}: {err}", file.display()))?;
+    let keys = if file.extension().is_some_and(|ext| ext == "json") {
+        keyfile::parse_json(&source)?
+    } else {
+        keyfile::parse_csv(&source)?
+    };
+    Ok((file, keys))
+}
+
+/// Generates a lookup function compiled to a balanced comparison tree
+///
+/// The generated `fn(&K) -> Option<u32>` (for any `Key` type `K`) returns the
+/// position of the key in the declared list. It loads the key as four words
+/// once, then branches on one word per level against pivots encoded as
+/// immediates, so a lookup over `n` distinct keys costs about `log2(n)`
+/// comparisons plus a final four-word equality check, with no table in
+/// memory.
+///
+/// Keys are given inline as base58 strings or as a key file path, in the
+/// formats accepted by [`include_keys!`]:
+///
+/// ```rust,ignore
+/// key_lookup! {
+///     /// Index of a supported quote mint
+///     pub fn quote_mint_index = [
+///         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+///         "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+///     ];
+/// }
+///
+/// key_lookup!(fn allowlist_index = "allowlist.json");
+/// ```
+#[proc_macro]
+pub fn key_lookup(input: TokenStream) -> TokenStream {
+    let LookupFn {
+        attrs,
+        vis,
+        name,
+        source,
+    } = parse_macro_input!(input as LookupFn);
+    let (keys, rebuild) = match source {
+        KeySource::Inline(literals) => {
+            let keys = literals
+                .iter()
+                .map(|literal| {
+                    keyfile::decode(&literal.value())
+                        .map_err(|message| syn::Error::new(literal.span(), message))
+                })
+                .collect::<Result<Vec<_>, _>>();
+            match keys {
+                Ok(keys) => (keys, None),
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+        KeySource::File(path) => match read_key_file(&path) {
+            Ok((file, keys)) => {
+                let file = file.to_string_lossy().into_owned();
+                (
+                    keys,
+                    Some(quote!(
+                        const _: &[u8] = ::core::include_bytes!(#file);
+                    )),
+                )
+            }
+            Err(message) => {
+                return syn::Error::new(path.span(), message)
+                    .to_compile_error()
+                    .into()
+            }
+        },
+    };
+    if let Err(message) = keyfile::sorted_unique(keys.clone()) {
+        return syn::Error::new(name.span(), message)
+            .to_compile_error()
+            .into();
+    }
+    let body = tree::lookup_tree(&keys);
+    quote! {
+        #(#attrs)*
+        #vis fn #name<K: ::solana_pubkey_compare::Key>(key: &K) -> ::core::option::Option<u32> {
+            #rebuild
+            let key = ::solana_pubkey_compare::Key::key_bytes(key);
+            let word = |at: usize| {
+                u64::from_le_bytes([
+                    key[at], key[at + 1], key[at + 2], key[at + 3],
+                    key[at + 4], key[at + 5], key[at + 6], key[at + 7],
+                ])
+            };
+            let w = [word(0), word(8), word(16), word(24)];
+            #body
+        }
+    }
+    .into()
+}
+
+/// `#[attrs] vis fn name = source;`
+struct LookupFn {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    name: Ident,
+    source: KeySource,
+}
+
+/// Keys listed inline, or the path of a key file
+enum KeySource {
+    Inline(Punctuated<LitStr, Token![,]>),
+    File(LitStr),
+}
+
+impl Parse for LookupFn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![fn]>()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let source = if input.peek(LitStr) {
+            KeySource::File(input.parse()?)
+        } else {
+            let content;
+            bracketed!(content in input);
+            KeySource::Inline(content.parse_terminated(<LitStr as Parse>::parse, Token![,])?)
+        };
+        input.parse::<Option<Token![;]>>()?;
+        Ok(LookupFn {
+            attrs,
+            vis,
+            name,
+            source,
+        })
+    }
+}
@@ -0,0 +1,69 @@
+//! Comparison-tree generation for `key_lookup!`
+//!
+//! Keys are treated as four little-endian words, the form a single `ldxdw`
+//! loads, and ordered by those words. Each inner node splits its keys on one
+//! word against an immediate pivot; each leaf checks all four words of its
+//! one candidate.
+
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+/// A key's words together with its position in the declared list
+type Entry = ([u64; 4], u32);
+
+/// Builds the body expression evaluating to the lookup result, given the
+/// key's words bound as `w`
+pub fn lookup_tree(keys: &[[u8; 32]]) -> TokenStream {
+    let mut entries: Vec<Entry> = keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| (words(key), index as u32))
+        .collect();
+    entries.sort_unstable();
+    node(&entries, 0)
+}
+
+fn words(key: &[u8; 32]) -> [u64; 4] {
+    core::array::from_fn(|i| u64::from_le_bytes(key[i * 8..i * 8 + 8].try_into().unwrap()))
+}
+
+/// Emits the subtree for `entries`, which are sorted and all share their
+/// words before `depth`
+fn node(entries: &[Entry], depth: usize) -> TokenStream {
+    match entries {
+        [] => quote!(::core::option::Option::None),
+        [(words, index)] => {
+            let [w0, w1, w2, w3] = words.map(Literal::u64_suffixed);
+            let index = Literal::u32_suffixed(*index);
+            quote! {
+                if w[0] == #w0 && w[1] == #w1 && w[2] == #w2 && w[3] == #w3 {
+                    ::core::option::Option::Some(#index)
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+        _ => {
+            let first = entries[0].0[depth];
+            if entries.iter().all(|(words, _)| words[depth] == first) {
+                return node(entries, depth + 1);
+            }
+            // Split at the word boundary nearest the middle, so each side
+            // holds whole runs of equal words
+            let mid = entries.len() / 2;
+            let boundary = |at: &usize| entries[*at - 1].0[depth] != entries[*at].0[depth];
+            let split = (mid..entries.len())
+                .find(boundary)
+                .into_iter()
+                .chain((1..mid).rev().find(boundary))
+                .min_by_key(|at| at.abs_diff(mid))
+                .unwrap();
+            let pivot = Literal::u64_suffixed(entries[split].0[depth]);
+            let lower = node(&entries[..split], depth);
+            let upper = node(&entries[split..], depth);
+            quote! {
+                if w[#depth] < #pivot { #lower } else { #upper }
+            }
+        }
+    }
+}
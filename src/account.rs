@@ -0,0 +1,127 @@
+//! Comparisons against keys stored inside account data
+//!
+//! Reading a key field out of an account normally means holding a
+//! `Ref<&mut [u8]>` from `try_borrow_data` for as long as the field is in
+//! use, which collides with any `try_borrow_mut_data` elsewhere in the
+//! instruction (typically around a CPI). The helpers here compare in place
+//! instead:
+//!
+//! - [`data_key_eq`] takes a shared borrow for the comparison alone and
+//!   releases it before returning.
+//! - [`data_key_eq_unborrowed`] never touches the `RefCell` borrow flag, for
+//!   code that must compare while a mutable borrow of the same data is alive.
+//!
+//! For loops over `remaining_accounts` that compare the same key many times,
+//! [`key_ptr_eq`] and [`position_key_ptr`] work on `*const Pubkey` obtained
+//! once up front; [`position_account`] is the safe wrapper.
+//!
+//! ```rust,ignore
+//! let mut data = vault.try_borrow_mut_data()?;
+//! // ... mutate other fields ...
+//! let authorized = unsafe { data_key_eq_unborrowed(vault, AUTHORITY_OFFSET, authority.key) };
+//! ```
+
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{Key, eq_raw};
+
+/// Compares the 32 bytes at `offset` in the account data with `key`
+///
+/// The data is borrowed only for the duration of the comparison. Returns
+/// `AccountBorrowFailed` if the data is currently mutably borrowed, and
+/// `AccountDataTooSmall` if the key would run past the end of the data.
+#[inline]
+pub fn data_key_eq<K: Key>(
+    account: &AccountInfo,
+    offset: usize,
+    key: &K,
+) -> Result<bool, ProgramError> {
+    let data = account.try_borrow_data()?;
+    match data.get(offset..).and_then(|rest| rest.get(..32)) {
+        Some(field) => Ok(unsafe { eq_raw(field.as_ptr(), key.key_ptr()) }),
+        None => Err(ProgramError::AccountDataTooSmall),
+    }
+}
+
+/// Compares the 32 bytes at `offset` in the account data with `key` without
+/// borrowing the data
+///
+/// Returns `false` if the key would run past the end of the data. No
+/// reference to the data is created, so an outstanding
+/// `try_borrow_mut_data` guard on the same account stays valid.
+///
+/// # Safety
+///
+/// The account data must not be written or reallocated during the call.
+#[inline]
+pub unsafe fn data_key_eq_unborrowed<K: Key>(
+    account: &AccountInfo,
+    offset: usize,
+    key: &K,
+) -> bool {
+    let data: *mut [u8] = unsafe { core::ptr::addr_of_mut!(**account.data.as_ptr()) };
+    match offset.checked_add(32) {
+        Some(end) if end <= data.len() => unsafe {
+            eq_raw((data as *const u8).add(offset), key.key_ptr())
+        },
+        _ => false,
+    }
+}
+
+/// Compares two keys through raw pointers
+///
+/// # Safety
+///
+/// Both pointers must be valid for reads of a `Pubkey` for the duration of
+/// the call. Pointers taken from `AccountInfo::key` satisfy this for as long
+/// as the `AccountInfo` slice they came from is alive.
+#[inline(always)]
+pub unsafe fn key_ptr_eq(lhs: *const Pubkey, rhs: *const Pubkey) -> bool {
+    unsafe { eq_raw(lhs as *const u8, rhs as *const u8) }
+}
+
+/// Returns the index of the first pointer in `keys` whose key equals the key
+/// at `target`
+///
+/// # Safety
+///
+/// Every pointer in `keys`, and `target`, must satisfy the requirements of
+/// [`key_ptr_eq`].
+#[inline]
+pub unsafe fn position_key_ptr(keys: &[*const Pubkey], target: *const Pubkey) -> Option<usize> {
+    keys.iter()
+        .position(|&key| unsafe { key_ptr_eq(key, target) })
+}
+
+/// Returns the index of the first account whose key equals `key`
+///
+/// Safe wrapper over [`key_ptr_eq`]: each pointer is taken from a live
+/// `&Pubkey` borrowed from `accounts`, so the safety requirements hold
+/// throughout the loop.
+///
+/// ```rust,ignore
+/// let Some(index) = position_account(ctx.remaining_accounts, &mint) else {
+///     return Err(ProgramError::NotEnoughAccountKeys);
+/// };
+/// ```
+#[inline]
+pub fn position_account(accounts: &[AccountInfo], key: &Pubkey) -> Option<usize> {
+    let target: *const Pubkey = key;
+    accounts
+        .iter()
+        .position(|account| unsafe { key_ptr_eq(account.key, target) })
+}
+
+/// Returns the index of the first account that matches `key` and signed the
+/// transaction
+///
+/// For programs that accept an authority at any position rather than a
+/// fixed slot. An account matching `key` without `is_signer` is skipped, so
+/// a later signing duplicate of the same key is still found.
+#[inline]
+pub fn signer_index_of(accounts: &[AccountInfo], key: &Pubkey) -> Option<usize> {
+    let target: *const Pubkey = key;
+    accounts
+        .iter()
+        .position(|account| account.is_signer && unsafe { key_ptr_eq(account.key, target) })
+}
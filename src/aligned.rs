@@ -0,0 +1,116 @@
+//! [`AlignedPubkey`]: a key with 8-byte alignment, and its comparison
+
+use crate::Key;
+
+/// A 32-byte key stored at an 8-byte-aligned address
+///
+/// The alignment lives in the type, so [`fast_eq_aligned`] and
+/// [`AlignedPubkey::as_words`] can rely on it without a runtime check. Use it
+/// for key fields of `repr(C)` zero-copy structs and for keys held on the
+/// stack.
+///
+/// The in-memory bytes are exactly the key bytes, so it implements [`Key`]
+/// and works with every routine in this crate.
+#[derive(Clone, Copy, Default)]
+#[repr(C, align(8))]
+pub struct AlignedPubkey(pub [u8; 32]);
+
+// SAFETY: 32 key bytes with 8-byte alignment leaves no padding.
+unsafe impl Key for AlignedPubkey {}
+
+impl AlignedPubkey {
+    /// Wraps `bytes`
+    #[inline(always)]
+    pub const fn new(bytes: [u8; 32]) -> Self {
+        AlignedPubkey(bytes)
+    }
+
+    /// Copies `key` into an aligned key
+    #[inline(always)]
+    pub fn from_key<K: Key>(key: &K) -> Self {
+        AlignedPubkey(*key.key_bytes())
+    }
+
+    /// Returns the key as four native-endian words
+    #[inline(always)]
+    pub fn as_words(&self) -> &[u64; 4] {
+        // SAFETY: `Self` is 32 bytes aligned to 8, the size and alignment of
+        // `[u64; 4]`, and every bit pattern is a valid `u64`.
+        unsafe { &*(self as *const Self as *const [u64; 4]) }
+    }
+
+    /// Returns the key as a `Pubkey`
+    #[cfg(feature = "solana-pubkey")]
+    #[inline(always)]
+    pub fn as_pubkey(&self) -> &solana_pubkey::Pubkey {
+        // SAFETY: `Pubkey` is a `repr(transparent)` wrapper around `[u8; 32]`.
+        unsafe { &*(self as *const Self as *const solana_pubkey::Pubkey) }
+    }
+}
+
+impl PartialEq for AlignedPubkey {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        fast_eq_aligned(self, other)
+    }
+}
+
+impl Eq for AlignedPubkey {}
+
+impl core::fmt::Debug for AlignedPubkey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AlignedPubkey").field(&self.0).finish()
+    }
+}
+
+impl From<[u8; 32]> for AlignedPubkey {
+    #[inline(always)]
+    fn from(bytes: [u8; 32]) -> Self {
+        AlignedPubkey(bytes)
+    }
+}
+
+impl From<AlignedPubkey> for [u8; 32] {
+    #[inline(always)]
+    fn from(key: AlignedPubkey) -> Self {
+        key.0
+    }
+}
+
+#[cfg(feature = "solana-pubkey")]
+impl From<AlignedPubkey> for solana_pubkey::Pubkey {
+    #[inline(always)]
+    fn from(key: AlignedPubkey) -> Self {
+        solana_pubkey::Pubkey::new_from_array(key.0)
+    }
+}
+
+/// Equality of two keys whose alignment is guaranteed by their type
+///
+/// On Solana BPF this is the [`fast_eq`](crate::fast_eq) routine: sBPF
+/// loads cost the same at any address, so its 5 to 14 instructions are
+/// already the minimum and alignment cannot lower them. Natively the keys
+/// are compared as aligned 64-bit words, with no unaligned loads or `memcmp`
+/// call.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::{AlignedPubkey, fast_eq_aligned};
+///
+/// let stored = AlignedPubkey::new([3u8; 32]);
+/// assert!(fast_eq_aligned(&stored, &AlignedPubkey::new([3u8; 32])));
+/// ```
+#[inline(always)]
+pub fn fast_eq_aligned(lhs: &AlignedPubkey, rhs: &AlignedPubkey) -> bool {
+    #[cfg(target_os = "solana")]
+    {
+        crate::fast_eq(lhs, rhs)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let (lhs, rhs) = (lhs.as_words(), rhs.as_words());
+        (lhs[0] ^ rhs[0]) | (lhs[1] ^ rhs[1]) | (lhs[2] ^ rhs[2]) | (lhs[3] ^ rhs[3]) == 0
+    }
+}
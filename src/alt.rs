@@ -0,0 +1,72 @@
+//! Address lookup table references
+//!
+//! Programs that accept keys as compressed `(lookup table, u8 index)` pairs
+//! can validate them against the table account's raw data in place, without
+//! deserializing the table or copying keys out.
+
+use crate::{Key, fast_eq_at, position_of};
+
+/// Size of the lookup table metadata that precedes the address list
+pub const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+/// Number of addresses a `u8` lookup table index can reach
+pub const LOOKUP_TABLE_MAX_ADDRESSES: usize = 256;
+
+/// Byte offset of address `index` within lookup table account data
+#[inline(always)]
+pub const fn alt_key_offset(index: u8) -> usize {
+    LOOKUP_TABLE_META_SIZE + index as usize * 32
+}
+
+/// Returns the address stored at `index` in lookup table account data
+///
+/// Returns `None` if the table holds fewer than `index + 1` addresses.
+#[inline]
+pub fn alt_key_at(table_data: &[u8], index: u8) -> Option<&[u8; 32]> {
+    let offset = alt_key_offset(index);
+    table_data.get(offset..offset + 32)?.try_into().ok()
+}
+
+/// Returns `true` if the address at `index` in lookup table data equals `key`
+///
+/// An out-of-range index never matches.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::alt::{alt_key_eq, alt_key_offset};
+///
+/// let mut table = vec![0u8; alt_key_offset(3)];
+/// table[alt_key_offset(2)..].copy_from_slice(&[8u8; 32]);
+///
+/// assert!(alt_key_eq(&table, 2, &[8u8; 32]));
+/// assert!(!alt_key_eq(&table, 3, &[8u8; 32]));
+/// ```
+#[inline(always)]
+pub fn alt_key_eq<K: Key>(table_data: &[u8], index: u8, key: &K) -> bool {
+    fast_eq_at(table_data, alt_key_offset(index), key)
+}
+
+/// Returns the lookup table index of `target` among `table_keys`
+///
+/// Resolves the `u8` index a transaction or instruction uses to reference
+/// `target` through the table. Only the first
+/// [`LOOKUP_TABLE_MAX_ADDRESSES`] keys are addressable, so later entries
+/// never match. The search is the [`position_of`] assembly loop, which
+/// costs 4 instructions per entry that differs from `target` in its first
+/// 8 bytes; a full 256-entry miss stays near 1,000 instructions.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::alt::alt_index_of;
+///
+/// let table = [[1u8; 32], [2u8; 32], [3u8; 32]];
+/// assert_eq!(alt_index_of(&table, &[3u8; 32]), Some(2));
+/// assert_eq!(alt_index_of(&table, &[4u8; 32]), None);
+/// ```
+#[inline]
+pub fn alt_index_of<K: Key>(table_keys: &[K], target: &K) -> Option<u8> {
+    let addressable = &table_keys[..table_keys.len().min(LOOKUP_TABLE_MAX_ADDRESSES)];
+    position_of(addressable, target).map(|index| index as u8)
+}
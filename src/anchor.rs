@@ -0,0 +1,147 @@
+//! Anchor integration for the validation helpers
+//!
+//! Anchor's `#[error_code]` enums convert into both `anchor_lang::error::Error`
+//! and `u32`, adding Anchor's error code offset (6000) on the way. The helpers
+//! here accept any such variant, so a failed check surfaces as the program's
+//! own documented error code instead of a generic `InvalidArgument`.
+//!
+//! ```rust,ignore
+//! use anchor_lang::prelude::*;
+//! use solana_pubkey_compare::anchor::require_keys_eq;
+//!
+//! #[error_code]
+//! pub enum VaultError {
+//!     #[msg("Signer is not the vault authority")]
+//!     Unauthorized,
+//! }
+//!
+//! pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+//!     let vault = &ctx.accounts.vault;
+//!     require_keys_eq(&ctx.accounts.authority.key(), &vault.authority, VaultError::Unauthorized)?;
+//!     Ok(())
+//! }
+//! ```
+
+use anchor_lang::{
+    AccountDeserialize, AccountSerialize, Owner, ZeroCopy,
+    accounts::interface_account::InterfaceAccount,
+    error::Error,
+    prelude::{
+        Account, AccountInfo, AccountLoader, Pubkey, Signer, SystemAccount, UncheckedAccount,
+    },
+};
+
+use crate::{FailureMode, Key, KeyOf, diag::log_failure, fast_eq};
+
+impl<'info, T: AccountSerialize + AccountDeserialize + Clone> KeyOf for Account<'info, T> {
+    type Key = Pubkey;
+
+    #[inline(always)]
+    fn key_of(&self) -> &Pubkey {
+        AsRef::<AccountInfo<'info>>::as_ref(self).key
+    }
+}
+
+impl<'info, T: ZeroCopy + Owner> KeyOf for AccountLoader<'info, T> {
+    type Key = Pubkey;
+
+    #[inline(always)]
+    fn key_of(&self) -> &Pubkey {
+        AsRef::<AccountInfo<'info>>::as_ref(self).key
+    }
+}
+
+impl<'info, T: AccountSerialize + AccountDeserialize + Clone> KeyOf for InterfaceAccount<'info, T> {
+    type Key = Pubkey;
+
+    #[inline(always)]
+    fn key_of(&self) -> &Pubkey {
+        AsRef::<AccountInfo<'info>>::as_ref(self).key
+    }
+}
+
+impl<'info> KeyOf for Signer<'info> {
+    type Key = Pubkey;
+
+    #[inline(always)]
+    fn key_of(&self) -> &Pubkey {
+        AsRef::<AccountInfo<'info>>::as_ref(self).key
+    }
+}
+
+impl<'info> KeyOf for SystemAccount<'info> {
+    type Key = Pubkey;
+
+    #[inline(always)]
+    fn key_of(&self) -> &Pubkey {
+        AsRef::<AccountInfo<'info>>::as_ref(self).key
+    }
+}
+
+impl<'info> KeyOf for UncheckedAccount<'info> {
+    type Key = Pubkey;
+
+    #[inline(always)]
+    fn key_of(&self) -> &Pubkey {
+        AsRef::<AccountInfo<'info>>::as_ref(self).key
+    }
+}
+
+impl FailureMode {
+    /// Returns `FailureMode::Error` carrying the code of an `#[error_code]`
+    /// variant, including Anchor's error code offset
+    #[inline(always)]
+    pub fn error_code<E: Into<u32>>(error: E) -> Self {
+        FailureMode::Error(error.into())
+    }
+
+    /// Returns `FailureMode::LogThenError` carrying the code of an
+    /// `#[error_code]` variant, including Anchor's error code offset
+    #[inline(always)]
+    pub fn log_error_code<E: Into<u32>>(error: E) -> Self {
+        FailureMode::LogThenError(error.into())
+    }
+}
+
+/// Both keys of a failed comparison, attached to the returned Anchor error so
+/// they appear in the program logs
+#[cold]
+fn with_keys<K: Key>(error: Error, lhs: &K, rhs: &K) -> Error {
+    error.with_pubkeys((
+        Pubkey::new_from_array(*lhs.key_bytes()),
+        Pubkey::new_from_array(*rhs.key_bytes()),
+    ))
+}
+
+/// Requires two keys to be equal, failing with the given Anchor error
+///
+/// The returned error carries both keys, which Anchor logs alongside the
+/// error name and code, mirroring `anchor_lang::require_keys_eq!`.
+#[inline(always)]
+pub fn require_keys_eq<K: Key, E: Into<Error>>(
+    lhs: &K,
+    rhs: &K,
+    error: E,
+) -> anchor_lang::Result<()> {
+    if fast_eq(lhs, rhs) {
+        Ok(())
+    } else {
+        log_failure("require_keys_eq", None, &[lhs.key_bytes(), rhs.key_bytes()]);
+        Err(with_keys(error.into(), lhs, rhs))
+    }
+}
+
+/// Requires two keys to differ, failing with the given Anchor error
+#[inline(always)]
+pub fn require_keys_neq<K: Key, E: Into<Error>>(
+    lhs: &K,
+    rhs: &K,
+    error: E,
+) -> anchor_lang::Result<()> {
+    if fast_eq(lhs, rhs) {
+        log_failure("require_keys_neq", None, &[lhs.key_bytes()]);
+        Err(with_keys(error.into(), lhs, rhs))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! BPF assembly fused compare-and-abort for 32-byte public keys
+//!
+//! The early-exit chain of `fast_eq`, but a mismatch calls the `abort`
+//! syscall directly instead of returning a result. The caller needs no
+//! branch on the return value, and no panic formatting is linked in for the
+//! failure path.
+//!
+//! `fast_assert_eq_with_code` runs the same chain, but first logs a
+//! caller-supplied code with `sol_log_64_` so the failure can be told apart
+//! in the transaction logs. A nested routine cannot make the program return
+//! a custom error, so the code is logged rather than returned.
+//!
+//! ## Performance Characteristics
+//! - **Keys equal**: 13 instructions
+//! - **Keys differ**: 4-13 instructions before the `abort` syscall, which
+//!   ends the program
+//! - **Memory ops**: 2-8 loads depending on where difference is found
+//! - **Branches**: 1-4 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each key at offset 0, compare, abort if different
+//! 2. Repeat for offsets 8, 16 and 24
+//! 3. Return if all chunks match
+//!
+//! ## Register Usage
+//! - r1: Pointer to first key (lhs_ptr parameter); log argument on mismatch
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk (`fast_assert_eq`), or the
+//!   error code (`fast_assert_eq_with_code`)
+//! - r4: Temporary for second key's 8-byte chunk
+//! - r5: Temporary for first key's 8-byte chunk (`fast_assert_eq_with_code`)
+
+.section .text
+.globl __solana_pubkey_compare__fast_assert_eq
+.type __solana_pubkey_compare__fast_assert_eq, @function
+
+__solana_pubkey_compare__fast_assert_eq:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: nothing; does not return if the keys differ
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = first 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = first 8 bytes of rhs
+    jne r3, r4, mismatch  // Abort if chunks differ
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, mismatch  // Abort if chunks differ
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, mismatch  // Abort if chunks differ
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, mismatch  // Abort if chunks differ
+
+    // All 32 bytes match
+    exit                  // Return to caller
+
+mismatch:
+    // Keys differ - the syscall fails the transaction and never returns
+    call abort
+
+.size __solana_pubkey_compare__fast_assert_eq, .-__solana_pubkey_compare__fast_assert_eq
+
+.globl __solana_pubkey_compare__fast_assert_eq_with_code
+.type __solana_pubkey_compare__fast_assert_eq_with_code, @function
+
+__solana_pubkey_compare__fast_assert_eq_with_code:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr, r3 = code
+    // Returns: nothing; does not return if the keys differ
+
+    // Bytes 0-7
+    ldxdw r5, [r1+0]      // r5 = first 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = first 8 bytes of rhs
+    jne r5, r4, mismatch_code // Log and abort if chunks differ
+
+    // Bytes 8-15
+    ldxdw r5, [r1+8]      // r5 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r5, r4, mismatch_code // Log and abort if chunks differ
+
+    // Bytes 16-23
+    ldxdw r5, [r1+16]     // r5 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r5, r4, mismatch_code // Log and abort if chunks differ
+
+    // Bytes 24-31
+    ldxdw r5, [r1+24]     // r5 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r5, r4, mismatch_code // Log and abort if chunks differ
+
+    // All 32 bytes match
+    exit                  // Return to caller
+
+mismatch_code:
+    // Keys differ - log the code as the first of five values, then abort
+    mov64 r1, r3
+    mov64 r2, 0
+    mov64 r3, 0
+    mov64 r4, 0
+    mov64 r5, 0
+    call sol_log_64_
+    call abort
+
+.size __solana_pubkey_compare__fast_assert_eq_with_code, .-__solana_pubkey_compare__fast_assert_eq_with_code
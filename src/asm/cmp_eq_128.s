@@ -0,0 +1,121 @@
+//! BPF assembly equality for 128-byte values
+//!
+//! The `fast_eq` early-exit chain over sixteen 64-bit chunks, the
+//! `fast_eq_n` body for 128-byte values such as signature pairs.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 5 instructions (values differ in first 8 bytes)
+//! - **Worst case**: 50 instructions (values are identical)
+//! - **Memory ops**: 2-32 loads depending on where difference is found
+//! - **Branches**: 1-16 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each value at offset 0, compare, exit if different
+//! 2. Repeat for offsets 8 through 120
+//! 3. Return true (1) if all chunks match
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first value (lhs_ptr parameter)
+//! - r2: Pointer to second value (rhs_ptr parameter)
+//! - r3: Temporary for first value's 8-byte chunk
+//! - r4: Temporary for second value's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_128
+.type __solana_pubkey_compare__fast_eq_128, @function
+
+__solana_pubkey_compare__fast_eq_128:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = bytes 0-7 of lhs
+    ldxdw r4, [r2+0]      // r4 = bytes 0-7 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 32-39
+    ldxdw r3, [r1+32]     // r3 = bytes 32-39 of lhs
+    ldxdw r4, [r2+32]     // r4 = bytes 32-39 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 40-47
+    ldxdw r3, [r1+40]     // r3 = bytes 40-47 of lhs
+    ldxdw r4, [r2+40]     // r4 = bytes 40-47 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 48-55
+    ldxdw r3, [r1+48]     // r3 = bytes 48-55 of lhs
+    ldxdw r4, [r2+48]     // r4 = bytes 48-55 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 56-63
+    ldxdw r3, [r1+56]     // r3 = bytes 56-63 of lhs
+    ldxdw r4, [r2+56]     // r4 = bytes 56-63 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 64-71
+    ldxdw r3, [r1+64]     // r3 = bytes 64-71 of lhs
+    ldxdw r4, [r2+64]     // r4 = bytes 64-71 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 72-79
+    ldxdw r3, [r1+72]     // r3 = bytes 72-79 of lhs
+    ldxdw r4, [r2+72]     // r4 = bytes 72-79 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 80-87
+    ldxdw r3, [r1+80]     // r3 = bytes 80-87 of lhs
+    ldxdw r4, [r2+80]     // r4 = bytes 80-87 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 88-95
+    ldxdw r3, [r1+88]     // r3 = bytes 88-95 of lhs
+    ldxdw r4, [r2+88]     // r4 = bytes 88-95 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 96-103
+    ldxdw r3, [r1+96]     // r3 = bytes 96-103 of lhs
+    ldxdw r4, [r2+96]     // r4 = bytes 96-103 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 104-111
+    ldxdw r3, [r1+104]    // r3 = bytes 104-111 of lhs
+    ldxdw r4, [r2+104]    // r4 = bytes 104-111 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 112-119
+    ldxdw r3, [r1+112]    // r3 = bytes 112-119 of lhs
+    ldxdw r4, [r2+112]    // r4 = bytes 112-119 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 120-127
+    ldxdw r3, [r1+120]    // r3 = bytes 120-127 of lhs
+    ldxdw r4, [r2+120]    // r4 = bytes 120-127 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // All 128 bytes match - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // Values differ - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_eq_128, .-__solana_pubkey_compare__fast_eq_128
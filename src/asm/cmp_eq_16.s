@@ -0,0 +1,50 @@
+//! BPF assembly equality for 16-byte values such as truncated hashes
+//!
+//! The `fast_eq` early-exit chain over two 64-bit chunks.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 5 instructions (values differ in first 8 bytes)
+//! - **Worst case**: 8 instructions (values are identical)
+//! - **Memory ops**: 2-4 loads depending on where difference is found
+//! - **Branches**: 1-2 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each value at offset 0, compare, exit if different
+//! 2. Compare the chunk at offset 8
+//! 3. Return true (1) if all chunks match
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first value (lhs_ptr parameter)
+//! - r2: Pointer to second value (rhs_ptr parameter)
+//! - r3: Temporary for first value's 8-byte chunk
+//! - r4: Temporary for second value's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_16
+.type __solana_pubkey_compare__fast_eq_16, @function
+
+__solana_pubkey_compare__fast_eq_16:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = bytes 0-7 of lhs
+    ldxdw r4, [r2+0]      // r4 = bytes 0-7 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // All 16 bytes match - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // Values differ - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_eq_16, .-__solana_pubkey_compare__fast_eq_16
@@ -0,0 +1,81 @@
+//! BPF assembly equality for 64-byte values such as signatures
+//!
+//! The `fast_eq` early-exit chain extended to eight 64-bit chunks, so a
+//! signature compares in one call instead of two 32-byte halves.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 5 instructions (values differ in first 8 bytes)
+//! - **Worst case**: 26 instructions (values are identical)
+//! - **Memory ops**: 2-16 loads depending on where difference is found
+//! - **Branches**: 1-8 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each value at offset 0, compare, exit if different
+//! 2. Repeat for offsets 8 through 56
+//! 3. Return true (1) if all chunks match
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first value (lhs_ptr parameter)
+//! - r2: Pointer to second value (rhs_ptr parameter)
+//! - r3: Temporary for first value's 8-byte chunk
+//! - r4: Temporary for second value's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_64
+.type __solana_pubkey_compare__fast_eq_64, @function
+
+__solana_pubkey_compare__fast_eq_64:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = bytes 0-7 of lhs
+    ldxdw r4, [r2+0]      // r4 = bytes 0-7 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 32-39
+    ldxdw r3, [r1+32]     // r3 = bytes 32-39 of lhs
+    ldxdw r4, [r2+32]     // r4 = bytes 32-39 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 40-47
+    ldxdw r3, [r1+40]     // r3 = bytes 40-47 of lhs
+    ldxdw r4, [r2+40]     // r4 = bytes 40-47 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 48-55
+    ldxdw r3, [r1+48]     // r3 = bytes 48-55 of lhs
+    ldxdw r4, [r2+48]     // r4 = bytes 48-55 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 56-63
+    ldxdw r3, [r1+56]     // r3 = bytes 56-63 of lhs
+    ldxdw r4, [r2+56]     // r4 = bytes 56-63 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // All 64 bytes match - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // Values differ - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_eq_64, .-__solana_pubkey_compare__fast_eq_64
@@ -0,0 +1,40 @@
+//! BPF assembly equality for 8-byte values such as discriminators
+//!
+//! Anchor accounts and instructions start with an 8-byte discriminator that
+//! is checked on every call. Both values fit one 64-bit load, so the check
+//! is a single load-and-compare.
+//!
+//! ## Performance Characteristics
+//! - **Equal**: 5 instructions
+//! - **Different**: 6 instructions
+//! - **Memory ops**: always 2 loads
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each value
+//! 2. Return 1 if they are equal, 0 otherwise
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first value (lhs_ptr parameter)
+//! - r2: Pointer to second value (rhs_ptr parameter)
+//! - r3: First value
+//! - r4: Second value
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_8
+.type __solana_pubkey_compare__fast_eq_8, @function
+
+__solana_pubkey_compare__fast_eq_8:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+
+    ldxdw r3, [r1+0]      // r3 = lhs
+    ldxdw r4, [r2+0]      // r4 = rhs
+    mov64 r0, 1           // Assume equal
+    jeq r3, r4, done
+    mov64 r0, 0           // Values differ
+
+done:
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_eq_8, .-__solana_pubkey_compare__fast_eq_8
@@ -0,0 +1,72 @@
+//! BPF assembly lexicographic ordering of two 32-byte public keys
+//!
+//! Finds the first differing 8-byte chunk with the same early-exit chain as
+//! `fast_eq`, then byte-swaps only that pair to big-endian so an unsigned
+//! compare orders them like their bytes, matching `Pubkey`'s `Ord`.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 8 instructions (first chunk differs, lhs greater)
+//! - **First chunk differs, lhs less**: 9 instructions
+//! - **Equal keys**: 14 instructions
+//! - **Worst case**: 18 instructions (last chunk differs, lhs less)
+//! - **Memory ops**: 2-8 loads depending on where difference is found
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each key at offset 0, jump to `differ` if different
+//! 2. Repeat for offsets 8, 16 and 24
+//! 3. Return 0 if all chunks match
+//! 4. At `differ`, convert both chunks to big-endian and return 1 if lhs is
+//!    greater, -1 otherwise
+//!
+//! ## Register Usage
+//! - r0: Return value as `Ordering` (-1 = less, 0 = equal, 1 = greater)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_cmp
+.type __solana_pubkey_compare__fast_cmp, @function
+
+__solana_pubkey_compare__fast_cmp:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = -1, 0 or 1
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = first 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = first 8 bytes of rhs
+    jne r3, r4, differ    // First difference is in this chunk
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, differ    // First difference is in this chunk
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, differ    // First difference is in this chunk
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, differ    // First difference is in this chunk
+
+    // All 32 bytes match - return equal
+    mov64 r0, 0
+    exit                  // Return to caller
+
+differ:
+    // r3 and r4 hold the first differing chunks; loads are little-endian,
+    // so swap to big-endian to compare in byte order
+    be64 r3
+    be64 r4
+    mov64 r0, 1           // Assume lhs is greater
+    jgt r3, r4, done
+    mov64 r0, -1          // lhs is less
+
+done:
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_cmp, .-__solana_pubkey_compare__fast_cmp
@@ -0,0 +1,77 @@
+//! BPF assembly index of the first differing byte of two 32-byte keys
+//!
+//! Locates the first differing 8-byte chunk with the `fast_eq` chain, then
+//! finds the differing byte inside it. Loads are little-endian, so the first
+//! differing byte is the lowest non-zero byte of the chunks' XOR.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 9 instructions (byte 0 differs)
+//! - **Equal keys**: 18 instructions
+//! - **Each further byte inside the differing chunk**: 6 instructions
+//! - **Worst case**: 63 instructions (only byte 31 differs)
+//!
+//! ## Algorithm
+//! 1. For each chunk, set r0 to its byte offset, compare, and jump to
+//!    `found` on a mismatch
+//! 2. Return 32 if every chunk matches
+//! 3. At `found`, XOR the chunks and shift out zero bytes, counting them
+//!    into r0
+//!
+//! ## Register Usage
+//! - r0: Return value (index of the first differing byte, 32 if equal)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: First key's chunk, then the chunks' XOR
+//! - r4: Second key's chunk, then the XOR's low byte
+
+.section .text
+.globl __solana_pubkey_compare__diff_index
+.type __solana_pubkey_compare__diff_index, @function
+
+__solana_pubkey_compare__diff_index:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = index of the first differing byte, or 32 if equal
+
+    // Bytes 0-7
+    mov64 r0, 0           // Chunk starts at byte 0
+    ldxdw r3, [r1+0]      // r3 = first 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = first 8 bytes of rhs
+    jne r3, r4, found     // First difference is in this chunk
+
+    // Bytes 8-15
+    mov64 r0, 8
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, found     // First difference is in this chunk
+
+    // Bytes 16-23
+    mov64 r0, 16
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, found     // First difference is in this chunk
+
+    // Bytes 24-31
+    mov64 r0, 24
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, found     // First difference is in this chunk
+
+    // All 32 bytes match
+    mov64 r0, 32
+    exit                  // Return to caller
+
+found:
+    xor64 r3, r4          // r3 = differing bits, non-zero
+
+next_byte:
+    mov64 r4, r3
+    and64 r4, 0xff        // r4 = lowest remaining byte of the XOR
+    jne r4, 0, done       // It differs: r0 is its index
+    rsh64 r3, 8           // Drop the equal byte
+    add64 r0, 1
+    ja next_byte
+
+done:
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__diff_index, .-__solana_pubkey_compare__diff_index
@@ -22,6 +22,11 @@
 //! 4. Load 8 bytes from each key at offset 24, compare, exit if different
 //! 5. Return true (1) if all chunks match
 //!
+//! ## Alignment
+//! sBPF memory loads have no alignment requirement: `ldxdw` reads 8 bytes
+//! from any address at the same cost, so keys may sit at odd offsets inside
+//! packed account data.
+//!
 //! ## Register Usage
 //! - r0: Return value (0 = false, 1 = true)
 //! - r1: Pointer to first key (lhs_ptr parameter) 
@@ -0,0 +1,76 @@
+//! Branchless BPF assembly implementation for 32-byte public key comparison
+//!
+//! Instead of exiting on the first mismatching chunk, this routine XORs every
+//! 64-bit word pair and ORs the differences into a single accumulator. The
+//! accumulator is zero exactly when all 32 bytes match, and it is folded into
+//! a 0/1 result without any conditional jumps.
+//!
+//! ## Performance Characteristics
+//! - **Every case**: 21 instructions (no data-dependent control flow)
+//! - **Memory ops**: always 8 loads
+//! - **Branches**: none
+//!
+//! ## Instruction Breakdown
+//! - 2x `ldxdw` + 1x `xor64` per 8-byte chunk
+//! - 3x `or64` to merge the four chunk differences
+//! - `mov64` + `sub64` + `or64` + `rsh64` + `xor64` to turn "accumulator is
+//!   zero" into 1 and anything else into 0
+//!
+//! ## Algorithm
+//! 1. r0 = lhs[0..8] ^ rhs[0..8]
+//! 2. r0 |= lhs[8..16] ^ rhs[8..16], then bytes 16-23 and 24-31
+//! 3. r0 = ((r0 | -r0) >> 63) ^ 1, i.e. 1 iff r0 == 0
+//!
+//! ## Register Usage
+//! - r0: Difference accumulator, then return value (0 = false, 1 = true)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_branchless
+.type __solana_pubkey_compare__fast_eq_branchless, @function
+// `fast_eq_u64` shares the body: r0 is already exactly 0 or 1
+.globl __solana_pubkey_compare__fast_eq_u64
+.type __solana_pubkey_compare__fast_eq_u64, @function
+
+__solana_pubkey_compare__fast_eq_branchless:
+__solana_pubkey_compare__fast_eq_u64:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+
+    // Bytes 0-7 seed the accumulator
+    ldxdw r0, [r1+0]      // r0 = first 8 bytes of lhs
+    ldxdw r3, [r2+0]      // r3 = first 8 bytes of rhs
+    xor64 r0, r3          // r0 = differing bits of chunk 0
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    xor64 r3, r4          // r3 = differing bits of chunk 1
+    or64 r0, r3           // Accumulate
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    xor64 r3, r4          // r3 = differing bits of chunk 2
+    or64 r0, r3           // Accumulate
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    xor64 r3, r4          // r3 = differing bits of chunk 3
+    or64 r0, r3           // Accumulate
+
+    // Fold the accumulator into a boolean without branching:
+    // (x | -x) has its top bit set for every non-zero x
+    mov64 r3, 0           // r3 = 0
+    sub64 r3, r0          // r3 = -acc
+    or64 r0, r3           // r0 = acc | -acc
+    rsh64 r0, 63          // r0 = 1 if acc != 0, else 0
+    xor64 r0, 1           // r0 = 1 if acc == 0, else 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_eq_branchless, .-__solana_pubkey_compare__fast_eq_branchless
+.size __solana_pubkey_compare__fast_eq_u64, .-__solana_pubkey_compare__fast_eq_u64
@@ -0,0 +1,96 @@
+//! BPF assembly test of one 32-byte key against two candidates
+//!
+//! Answers "is this the token program or the token-2022 program" in one
+//! call. The key is compared with the first candidate by the `fast_eq`
+//! chain; when that fails on the first chunk, the key's chunk is still in
+//! a register and the second candidate's comparison starts from it.
+//! Distinct candidates almost always differ in their first 8 bytes, so the
+//! shared load is the common case.
+//!
+//! ## Performance Characteristics
+//! - **Key equals first candidate**: 14 instructions
+//! - **Key equals second, candidates differ in first chunk**: 16 instructions
+//! - **Neither, key differs from both in first chunk**: 7 instructions
+//! - **Worst case**: 26 instructions (first candidate fails on the last
+//!   chunk, second matches)
+//!
+//! ## Algorithm
+//! 1. Compare key with `a` chunk by chunk; return 1 if all match
+//! 2. On a mismatch in chunk 0, compare the loaded key chunk with `b[0..8]`
+//!    and continue with chunks 1-3; on a later mismatch, compare with `b`
+//!    from chunk 0
+//! 3. Return 1 if `b` matches, 0 otherwise
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to the key
+//! - r2: Pointer to the first candidate
+//! - r3: Pointer to the second candidate
+//! - r4: Temporary for the key's 8-byte chunk
+//! - r5: Temporary for a candidate's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__eq_either
+.type __solana_pubkey_compare__eq_either, @function
+
+__solana_pubkey_compare__eq_either:
+    // Function parameters: r1 = key_ptr, r2 = a_ptr, r3 = b_ptr
+    // Returns: r0 = 1 if the key equals a or b, 0 otherwise
+
+    // Key against a, bytes 0-7
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of key
+    ldxdw r5, [r2+0]      // r5 = first 8 bytes of a
+    jne r4, r5, try_b_loaded // Not a; r4 still holds key[0..8]
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of key
+    ldxdw r5, [r2+8]      // r5 = bytes 8-15 of a
+    jne r4, r5, try_b     // Not a
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of key
+    ldxdw r5, [r2+16]     // r5 = bytes 16-23 of a
+    jne r4, r5, try_b     // Not a
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of key
+    ldxdw r5, [r2+24]     // r5 = bytes 24-31 of a
+    jne r4, r5, try_b     // Not a
+
+    // Key equals a - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+try_b:
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of key
+
+try_b_loaded:
+    // Key against b, bytes 0-7 (key chunk already in r4)
+    ldxdw r5, [r3+0]      // r5 = first 8 bytes of b
+    jne r4, r5, neither   // Not b either
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of key
+    ldxdw r5, [r3+8]      // r5 = bytes 8-15 of b
+    jne r4, r5, neither   // Not b either
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of key
+    ldxdw r5, [r3+16]     // r5 = bytes 16-23 of b
+    jne r4, r5, neither   // Not b either
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of key
+    ldxdw r5, [r3+24]     // r5 = bytes 24-31 of b
+    jne r4, r5, neither   // Not b either
+
+    // Key equals b - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+neither:
+    // Key matches neither candidate - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__eq_either, .-__solana_pubkey_compare__eq_either
@@ -0,0 +1,84 @@
+//! BPF assembly fused comparison of two key pairs
+//!
+//! Checks `a1 == b1 && a2 == b2` in one call, for the "check mint and
+//! owner" pattern of token programs. Both pairs use the `fast_eq` early-exit
+//! chain back to back, so the call overhead is paid once.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 5 instructions (first pair differs in first 8 bytes)
+//! - **Worst case**: 26 instructions (both pairs equal)
+//! - **Memory ops**: 2-16 loads depending on where a difference is found
+//! - **Branches**: 1-8 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Compare a1 with b1 chunk by chunk, return 0 on the first mismatch
+//! 2. Compare a2 with b2 the same way
+//! 3. Return 1 if every chunk of both pairs matches
+//!
+//! ## Register Usage
+//! - r0: Temporary for the first key's chunk, then return value
+//!   (0 = false, 1 = true)
+//! - r1: Pointer to a1
+//! - r2: Pointer to b1
+//! - r3: Pointer to a2
+//! - r4: Pointer to b2
+//! - r5: Temporary for the second key's chunk
+
+.section .text
+.globl __solana_pubkey_compare__eq_pairs
+.type __solana_pubkey_compare__eq_pairs, @function
+
+__solana_pubkey_compare__eq_pairs:
+    // Function parameters: r1 = a1_ptr, r2 = b1_ptr, r3 = a2_ptr, r4 = b2_ptr
+    // Returns: r0 = 1 if both pairs are equal, 0 otherwise
+
+    // Pair 1, bytes 0-7
+    ldxdw r0, [r1+0]      // r0 = bytes 0-7 of a1
+    ldxdw r5, [r2+0]      // r5 = bytes 0-7 of b1
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Pair 1, bytes 8-15
+    ldxdw r0, [r1+8]      // r0 = bytes 8-15 of a1
+    ldxdw r5, [r2+8]      // r5 = bytes 8-15 of b1
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Pair 1, bytes 16-23
+    ldxdw r0, [r1+16]     // r0 = bytes 16-23 of a1
+    ldxdw r5, [r2+16]     // r5 = bytes 16-23 of b1
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Pair 1, bytes 24-31
+    ldxdw r0, [r1+24]     // r0 = bytes 24-31 of a1
+    ldxdw r5, [r2+24]     // r5 = bytes 24-31 of b1
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Pair 2, bytes 0-7
+    ldxdw r0, [r3+0]      // r0 = bytes 0-7 of a2
+    ldxdw r5, [r4+0]      // r5 = bytes 0-7 of b2
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Pair 2, bytes 8-15
+    ldxdw r0, [r3+8]      // r0 = bytes 8-15 of a2
+    ldxdw r5, [r4+8]      // r5 = bytes 8-15 of b2
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Pair 2, bytes 16-23
+    ldxdw r0, [r3+16]     // r0 = bytes 16-23 of a2
+    ldxdw r5, [r4+16]     // r5 = bytes 16-23 of b2
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Pair 2, bytes 24-31
+    ldxdw r0, [r3+24]     // r0 = bytes 24-31 of a2
+    ldxdw r5, [r4+24]     // r5 = bytes 24-31 of b2
+    jne r0, r5, not_equal // Early exit if chunks differ
+
+    // Both pairs match - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // A pair differs - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__eq_pairs, .-__solana_pubkey_compare__eq_pairs
@@ -0,0 +1,70 @@
+//! BPF assembly key equality with a pointer-identity short-circuit
+//!
+//! Checks whether both arguments point at the same key before loading
+//! anything. Generated code often compares an account's key against itself;
+//! those calls return after 3 instructions instead of running all four
+//! chunk compares. Every other call pays one extra instruction over
+//! `fast_eq`.
+//!
+//! ## Performance Characteristics
+//! - **Same address**: 3 instructions
+//! - **Best case**: 6 instructions (keys differ in first 8 bytes)
+//! - **Worst case**: 15 instructions (distinct copies of the same key)
+//! - **Memory ops**: 0-8 loads depending on where difference is found
+//! - **Branches**: 1-5 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Return true (1) if both pointers are equal
+//! 2. Load 8 bytes from each key at offset 0, compare, exit if different
+//! 3. Repeat for offsets 8, 16 and 24
+//! 4. Return true (1) if all chunks match
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_ptrcheck
+.type __solana_pubkey_compare__fast_eq_ptrcheck, @function
+
+__solana_pubkey_compare__fast_eq_ptrcheck:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+
+    // Same key in memory - nothing to load
+    jeq r1, r2, equal     // Early exit if the pointers match
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = first 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = first 8 bytes of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+equal:
+    // All 32 bytes match - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // Keys differ - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_eq_ptrcheck, .-__solana_pubkey_compare__fast_eq_ptrcheck
@@ -0,0 +1,62 @@
+//! BPF assembly key equality scanning from the last chunk to the first
+//!
+//! The `fast_eq` early-exit chain in reverse order. Keys that share a prefix
+//! and differ in their trailing bytes, such as sequential seeds or
+//! counter-based derivations, exit on the first compare instead of the last.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 5 instructions (keys differ in last 8 bytes)
+//! - **Worst case**: 14 instructions (keys are identical)
+//! - **Memory ops**: 2-8 loads depending on where difference is found
+//! - **Branches**: 1-4 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each key at offset 24, compare, exit if different
+//! 2. Repeat for offsets 16, 8 and 0
+//! 3. Return true (1) if all chunks match
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_rev
+.type __solana_pubkey_compare__fast_eq_rev, @function
+
+__solana_pubkey_compare__fast_eq_rev:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = bytes 0-7 of lhs
+    ldxdw r4, [r2+0]      // r4 = bytes 0-7 of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+
+    // All 32 bytes match - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // Keys differ - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_eq_rev, .-__solana_pubkey_compare__fast_eq_rev
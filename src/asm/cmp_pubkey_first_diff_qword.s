@@ -0,0 +1,61 @@
+//! BPF assembly index of the first differing 8-byte lane of two keys
+//!
+//! A diagnostic for failed comparisons: returns which of the four 64-bit
+//! lanes differs first, with no byte search inside it. The lane number is
+//! loaded into r0 before each compare, so a mismatch exits directly.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 5 instructions (lane 0 differs)
+//! - **Worst case**: 18 instructions (keys are identical)
+//! - **Memory ops**: 2-8 loads depending on where the difference is found
+//!
+//! ## Algorithm
+//! 1. For each lane, set r0 to its index, compare, and exit on a mismatch
+//! 2. Return 4 if every lane matches
+//!
+//! ## Register Usage
+//! - r0: Return value (0-3 = first differing lane, 4 = equal)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__first_diff_qword
+.type __solana_pubkey_compare__first_diff_qword, @function
+
+__solana_pubkey_compare__first_diff_qword:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = first differing lane, or 4 if equal
+
+    // Lane 0: bytes 0-7
+    mov64 r0, 0
+    ldxdw r3, [r1+0]      // r3 = first 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = first 8 bytes of rhs
+    jne r3, r4, done      // Lane 0 differs
+
+    // Lane 1: bytes 8-15
+    mov64 r0, 1
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, done      // Lane 1 differs
+
+    // Lane 2: bytes 16-23
+    mov64 r0, 2
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, done      // Lane 2 differs
+
+    // Lane 3: bytes 24-31
+    mov64 r0, 3
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, done      // Lane 3 differs
+
+    // All lanes match
+    mov64 r0, 4
+
+done:
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__first_diff_qword, .-__solana_pubkey_compare__first_diff_qword
@@ -0,0 +1,52 @@
+//! BPF assembly all-zero test for a 32-byte public key
+//!
+//! Checks for `Pubkey::default()`, the usual marker of an unset key field,
+//! by comparing each 64-bit chunk against an immediate zero. No zero key has
+//! to be materialized and only one pointer is read.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 4 instructions (first 8 bytes non-zero)
+//! - **Worst case**: 10 instructions (key is all zeros)
+//! - **Memory ops**: 1-4 loads depending on where a set byte is found
+//! - **Branches**: 1-4 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes at offset 0, return 0 if non-zero
+//! 2. Repeat for offsets 8, 16 and 24
+//! 3. Return 1 if every chunk is zero
+//!
+//! ## Register Usage
+//! - r0: Return value (1 = all zeros, 0 = otherwise)
+//! - r1: Pointer to the key
+//! - r2: Temporary for the key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_is_zero
+.type __solana_pubkey_compare__fast_is_zero, @function
+
+__solana_pubkey_compare__fast_is_zero:
+    // Function parameters: r1 = key_ptr
+    // Returns: r0 = 1 if all 32 bytes are zero, 0 otherwise
+
+    ldxdw r2, [r1+0]      // r2 = bytes 0-7
+    jne r2, 0, nonzero    // Early exit on a set byte
+
+    ldxdw r2, [r1+8]      // r2 = bytes 8-15
+    jne r2, 0, nonzero    // Early exit on a set byte
+
+    ldxdw r2, [r1+16]     // r2 = bytes 16-23
+    jne r2, 0, nonzero    // Early exit on a set byte
+
+    ldxdw r2, [r1+24]     // r2 = bytes 24-31
+    jne r2, 0, nonzero    // Early exit on a set byte
+
+    // All 32 bytes are zero - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+nonzero:
+    // Some byte is set - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_is_zero, .-__solana_pubkey_compare__fast_is_zero
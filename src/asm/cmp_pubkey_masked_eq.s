@@ -0,0 +1,76 @@
+//! BPF assembly masked equality of two 32-byte keys
+//!
+//! Compares only the bits selected by a 32-byte mask, for addressing
+//! schemes that treat some key bytes as "don't care". Each chunk pair is
+//! XORed and the difference ANDed with the mask chunk before the zero test,
+//! so the mask costs one load and one AND per chunk.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 8 instructions (masked difference in first 8 bytes)
+//! - **Worst case**: 26 instructions (keys equal under the mask)
+//! - **Memory ops**: 3-12 loads depending on where a difference is found
+//! - **Branches**: 1-4 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. diff = (lhs[0..8] ^ rhs[0..8]) & mask[0..8]; return 0 if non-zero
+//! 2. Repeat for offsets 8, 16 and 24
+//! 3. Return 1
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Pointer to the mask
+//! - r4: First key's chunk, then the masked difference
+//! - r5: Second key's chunk, then the mask chunk
+
+.section .text
+.globl __solana_pubkey_compare__masked_eq
+.type __solana_pubkey_compare__masked_eq, @function
+
+__solana_pubkey_compare__masked_eq:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr, r3 = mask_ptr
+    // Returns: r0 = 1 if the keys are equal under the mask, 0 otherwise
+
+    // Bytes 0-7
+    ldxdw r4, [r1+0]      // r4 = bytes 0-7 of lhs
+    ldxdw r5, [r2+0]      // r5 = bytes 0-7 of rhs
+    xor64 r4, r5          // r4 = differing bits
+    ldxdw r5, [r3+0]      // r5 = bytes 0-7 of mask
+    and64 r4, r5          // Drop don't-care bits
+    jne r4, 0, not_equal  // Early exit on a masked difference
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of lhs
+    ldxdw r5, [r2+8]      // r5 = bytes 8-15 of rhs
+    xor64 r4, r5          // r4 = differing bits
+    ldxdw r5, [r3+8]      // r5 = bytes 8-15 of mask
+    and64 r4, r5          // Drop don't-care bits
+    jne r4, 0, not_equal  // Early exit on a masked difference
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of lhs
+    ldxdw r5, [r2+16]     // r5 = bytes 16-23 of rhs
+    xor64 r4, r5          // r4 = differing bits
+    ldxdw r5, [r3+16]     // r5 = bytes 16-23 of mask
+    and64 r4, r5          // Drop don't-care bits
+    jne r4, 0, not_equal  // Early exit on a masked difference
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of lhs
+    ldxdw r5, [r2+24]     // r5 = bytes 24-31 of rhs
+    xor64 r4, r5          // r4 = differing bits
+    ldxdw r5, [r3+24]     // r5 = bytes 24-31 of mask
+    and64 r4, r5          // Drop don't-care bits
+    jne r4, 0, not_equal  // Early exit on a masked difference
+
+    // No difference under the mask - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // Keys differ under the mask - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__masked_eq, .-__solana_pubkey_compare__masked_eq
@@ -0,0 +1,62 @@
+//! BPF assembly inequality test for 32-byte public keys
+//!
+//! The same early-exit chain as `fast_eq` with the return values swapped, so
+//! guard clauses rejecting a mismatch branch on the result directly instead
+//! of negating it first.
+//!
+//! ## Performance Characteristics
+//! - **Best case**: 5 instructions (keys differ in first 8 bytes)
+//! - **Worst case**: 14 instructions (keys are identical)
+//! - **Memory ops**: 2-8 loads depending on where difference is found
+//! - **Branches**: 1-4 conditional jumps with early termination
+//!
+//! ## Algorithm
+//! 1. Load 8 bytes from each key at offset 0, compare, return 1 if different
+//! 2. Repeat for offsets 8, 16 and 24
+//! 3. Return 0 if all chunks match
+//!
+//! ## Register Usage
+//! - r0: Return value (1 = keys differ, 0 = keys equal)
+//! - r1: Pointer to first key (lhs_ptr parameter)
+//! - r2: Pointer to second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__fast_ne
+.type __solana_pubkey_compare__fast_ne, @function
+
+__solana_pubkey_compare__fast_ne:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if not equal, 0 if equal
+
+    // Bytes 0-7
+    ldxdw r3, [r1+0]      // r3 = first 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = first 8 bytes of rhs
+    jne r3, r4, differ    // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of lhs
+    ldxdw r4, [r2+8]      // r4 = bytes 8-15 of rhs
+    jne r3, r4, differ    // Early exit if chunks differ
+
+    // Bytes 16-23
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of lhs
+    ldxdw r4, [r2+16]     // r4 = bytes 16-23 of rhs
+    jne r3, r4, differ    // Early exit if chunks differ
+
+    // Bytes 24-31
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of lhs
+    ldxdw r4, [r2+24]     // r4 = bytes 24-31 of rhs
+    jne r3, r4, differ    // Early exit if chunks differ
+
+    // All 32 bytes match - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+differ:
+    // Keys differ - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__fast_ne, .-__solana_pubkey_compare__fast_ne
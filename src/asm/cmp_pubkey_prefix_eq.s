@@ -0,0 +1,94 @@
+//! BPF assembly equality of the first `n` bytes of two 32-byte keys
+//!
+//! Whole 8-byte chunks inside the prefix use the `fast_eq` early-exit chain,
+//! each guarded by a check of `n`. A trailing partial chunk is loaded whole,
+//! which stays inside the 32-byte keys, XORed, and masked to its first
+//! `n % 8` bytes: loads are little-endian, so those are the low bits.
+//!
+//! ## Performance Characteristics
+//! - **n = 0**: 6 instructions
+//! - **n = 32, keys equal**: 18 instructions
+//! - **Partial chunk**: 17 instructions on top of the whole chunks before it
+//! - **Worst case**: 30 instructions (n = 25 to 31, prefixes equal)
+//! - **Memory ops**: 2 loads per compared chunk
+//!
+//! ## Algorithm
+//! 1. For each chunk fully inside the prefix, compare and exit if different
+//! 2. If `n` is a multiple of 8, return 1
+//! 3. Otherwise XOR the chunk at `n & !7`, mask to the low `8 * (n % 8)`
+//!    bits, and return 1 if the result is zero
+//!
+//! The caller guarantees `n <= 32`.
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Pointer to first key (lhs_ptr parameter), then the partial chunk
+//! - r2: Pointer to second key (rhs_ptr parameter), then the partial chunk
+//! - r3: Prefix length `n`, then the partial chunk's offset and difference
+//! - r4: Temporary for first key's chunk, then the partial chunk's bit count
+//! - r5: Temporary for second key's chunk, then the mask
+
+.section .text
+.globl __solana_pubkey_compare__prefix_eq
+.type __solana_pubkey_compare__prefix_eq, @function
+
+__solana_pubkey_compare__prefix_eq:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr, r3 = n
+    // Returns: r0 = 1 if the first n bytes are equal, 0 otherwise
+
+    // Bytes 0-7
+    jlt r3, 8, partial    // Chunk 0 is not fully inside the prefix
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of lhs
+    ldxdw r5, [r2+0]      // r5 = first 8 bytes of rhs
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    jlt r3, 16, partial
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of lhs
+    ldxdw r5, [r2+8]      // r5 = bytes 8-15 of rhs
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 16-23
+    jlt r3, 24, partial
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of lhs
+    ldxdw r5, [r2+16]     // r5 = bytes 16-23 of rhs
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 24-31
+    jlt r3, 32, partial
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of lhs
+    ldxdw r5, [r2+24]     // r5 = bytes 24-31 of rhs
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Whole key compared - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+partial:
+    mov64 r0, 1           // Assume the rest of the prefix matches
+    mov64 r4, r3
+    and64 r4, 7           // r4 = bytes left in the partial chunk
+    jeq r4, 0, done       // Prefix ends on a chunk boundary
+    lsh64 r4, 3           // r4 = bits left
+    and64 r3, -8          // r3 = offset of the partial chunk
+    add64 r1, r3
+    add64 r2, r3
+    ldxdw r3, [r1+0]      // r3 = partial chunk of lhs
+    ldxdw r5, [r2+0]      // r5 = partial chunk of rhs
+    xor64 r3, r5          // r3 = differing bits
+    mov64 r5, 1
+    lsh64 r5, r4
+    sub64 r5, 1           // r5 = mask of the prefix's low bytes
+    and64 r3, r5          // Keep only differences inside the prefix
+    jeq r3, 0, done
+    mov64 r0, 0           // Prefix differs
+
+done:
+    exit                  // Return to caller
+
+not_equal:
+    // A whole chunk differs - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__prefix_eq, .-__solana_pubkey_compare__prefix_eq
@@ -0,0 +1,74 @@
+//! BPF assembly compare-and-set for a 32-byte key slot
+//!
+//! Compares the slot with the expected key using the same early-exit chain
+//! as `fast_eq`, and only when all four words match overwrites the slot with
+//! the new key. Fusing the two avoids a second call and reloading pointers
+//! between the check and the write.
+//!
+//! ## Performance Characteristics
+//! - **Swap**: 23 instructions (full compare, then 4 load/store pairs)
+//! - **No swap, first chunk differs**: 5 instructions
+//! - **No swap, last chunk differs**: 14 instructions
+//! - **Memory ops**: up to 12 loads and 4 stores
+//!
+//! ## Algorithm
+//! 1. Compare slot[0..8] with expected[0..8]; on mismatch return 0
+//! 2. Repeat for bytes 8-15, 16-23 and 24-31
+//! 3. Copy new[0..32] into the slot one word at a time
+//! 4. Return 1
+//!
+//! The slot is a `&mut` in Rust, so it never overlaps `expected` or `new`.
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = slot unchanged, 1 = slot overwritten)
+//! - r1: Pointer to the slot
+//! - r2: Pointer to the expected key
+//! - r3: Pointer to the new key
+//! - r4: Temporary for the slot's 8-byte chunk, then the new key's chunk
+//! - r5: Temporary for the expected key's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__key_cas
+.type __solana_pubkey_compare__key_cas, @function
+
+__solana_pubkey_compare__key_cas:
+    // Function parameters: r1 = slot_ptr, r2 = expected_ptr, r3 = new_ptr
+    // Returns: r0 = 1 if the slot was overwritten, 0 otherwise
+
+    mov64 r0, 0           // Assume no swap
+
+    // Bytes 0-7
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of the slot
+    ldxdw r5, [r2+0]      // r5 = first 8 bytes of expected
+    jne r4, r5, unchanged // Slot differs from expected
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of the slot
+    ldxdw r5, [r2+8]      // r5 = bytes 8-15 of expected
+    jne r4, r5, unchanged // Slot differs from expected
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of the slot
+    ldxdw r5, [r2+16]     // r5 = bytes 16-23 of expected
+    jne r4, r5, unchanged // Slot differs from expected
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of the slot
+    ldxdw r5, [r2+24]     // r5 = bytes 24-31 of expected
+    jne r4, r5, unchanged // Slot differs from expected
+
+    // All chunks match: write the new key
+    ldxdw r4, [r3+0]      // r4 = bytes 0-7 of new
+    stxdw [r1+0], r4
+    ldxdw r4, [r3+8]      // r4 = bytes 8-15 of new
+    stxdw [r1+8], r4
+    ldxdw r4, [r3+16]     // r4 = bytes 16-23 of new
+    stxdw [r1+16], r4
+    ldxdw r4, [r3+24]     // r4 = bytes 24-31 of new
+    stxdw [r1+24], r4
+    mov64 r0, 1           // Swapped
+
+unchanged:
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__key_cas, .-__solana_pubkey_compare__key_cas
@@ -0,0 +1,54 @@
+//! Size-optimized BPF assembly for 32-byte public key comparison
+//!
+//! Selected instead of `src/asm/cmp_pubkey_eq.s` by the `opt-size` feature.
+//! The four unrolled chunk comparisons are rolled into a loop and constants
+//! are loaded with `mov64` rather than the 16-byte `lddw`, trading compute
+//! units for program size.
+//!
+//! ## Size and Performance Characteristics
+//! - **Encoding**: 12 instruction slots (96 bytes) vs 18 slots (144 bytes)
+//! - **Best case**: 6 instructions (keys differ in first 8 bytes)
+//! - **Worst case**: 31 instructions (keys are identical)
+//!
+//! ## Algorithm
+//! 1. Set a byte counter to 32
+//! 2. Load 8 bytes from each key, exit with 0 if they differ
+//! 3. Advance both pointers by 8, decrement the counter, loop while non-zero
+//! 4. Return true (1) if every chunk matched
+//!
+//! ## Register Usage
+//! - r0: Return value (0 = false, 1 = true)
+//! - r1: Cursor into first key (lhs_ptr parameter)
+//! - r2: Cursor into second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+//! - r5: Bytes remaining
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq
+.type __solana_pubkey_compare__fast_eq, @function
+
+__solana_pubkey_compare__fast_eq:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+    mov64 r5, 32          // 32 bytes left to compare
+
+loop:
+    ldxdw r3, [r1+0]      // r3 = next 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = next 8 bytes of rhs
+    jne r3, r4, not_equal // Early exit if chunks differ
+    add64 r1, 8           // Advance lhs cursor
+    add64 r2, 8           // Advance rhs cursor
+    sub64 r5, 8           // One chunk done
+    jne r5, 0, loop       // More chunks to compare
+
+    // All 32 bytes match - return true
+    mov64 r0, 1
+    exit
+
+not_equal:
+    // Keys differ - return false
+    mov64 r0, 0
+    exit
+
+.size __solana_pubkey_compare__fast_eq, .-__solana_pubkey_compare__fast_eq
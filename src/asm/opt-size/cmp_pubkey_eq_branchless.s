@@ -0,0 +1,58 @@
+//! Size-optimized branchless BPF assembly for 32-byte public key comparison
+//!
+//! Selected instead of `src/asm/cmp_pubkey_eq_branchless.s` by the `opt-size`
+//! feature. The XOR-accumulate is rolled into a loop; the only branch is the
+//! loop back-edge, which runs a fixed four times, so the cost stays
+//! independent of the key contents.
+//!
+//! ## Size and Performance Characteristics
+//! - **Encoding**: 16 instruction slots (128 bytes) vs 21 slots (168 bytes)
+//! - **Every case**: 40 instructions
+//!
+//! ## Algorithm
+//! 1. Clear the accumulator and set a byte counter to 32
+//! 2. XOR the next 8-byte chunk pair into the accumulator, four times
+//! 3. r0 = ((acc | -acc) >> 63) ^ 1, i.e. 1 iff acc == 0
+//!
+//! ## Register Usage
+//! - r0: Difference accumulator, then return value (0 = false, 1 = true)
+//! - r1: Cursor into first key (lhs_ptr parameter)
+//! - r2: Cursor into second key (rhs_ptr parameter)
+//! - r3: Temporary for first key's 8-byte chunk
+//! - r4: Temporary for second key's 8-byte chunk
+//! - r5: Bytes remaining
+
+.section .text
+.globl __solana_pubkey_compare__fast_eq_branchless
+.type __solana_pubkey_compare__fast_eq_branchless, @function
+// `fast_eq_u64` shares the body: r0 is already exactly 0 or 1
+.globl __solana_pubkey_compare__fast_eq_u64
+.type __solana_pubkey_compare__fast_eq_u64, @function
+
+__solana_pubkey_compare__fast_eq_branchless:
+__solana_pubkey_compare__fast_eq_u64:
+    // Function parameters: r1 = lhs_ptr, r2 = rhs_ptr
+    // Returns: r0 = 1 if equal, 0 if not equal
+    mov64 r0, 0           // Accumulator starts clean
+    mov64 r5, 32          // 32 bytes left to compare
+
+loop:
+    ldxdw r3, [r1+0]      // r3 = next 8 bytes of lhs
+    ldxdw r4, [r2+0]      // r4 = next 8 bytes of rhs
+    xor64 r3, r4          // r3 = differing bits of this chunk
+    or64 r0, r3           // Accumulate
+    add64 r1, 8           // Advance lhs cursor
+    add64 r2, 8           // Advance rhs cursor
+    sub64 r5, 8           // One chunk done
+    jne r5, 0, loop       // Fixed trip count: four iterations
+
+    // Fold the accumulator into a boolean without branching
+    mov64 r3, 0           // r3 = 0
+    sub64 r3, r0          // r3 = -acc
+    or64 r0, r3           // r0 = acc | -acc
+    rsh64 r0, 63          // r0 = 1 if acc != 0, else 0
+    xor64 r0, 1           // r0 = 1 if acc == 0, else 0
+    exit
+
+.size __solana_pubkey_compare__fast_eq_branchless, .-__solana_pubkey_compare__fast_eq_branchless
+.size __solana_pubkey_compare__fast_eq_u64, .-__solana_pubkey_compare__fast_eq_u64
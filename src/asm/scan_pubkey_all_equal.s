@@ -0,0 +1,77 @@
+//! BPF assembly check that every key in a slice equals the first
+//!
+//! Walks the slice once, comparing each element against element 0. The
+//! first key's leading 8 bytes stay in a register, so each element loads
+//! only its own first word and the other key's remaining three.
+//!
+//! ## Performance Characteristics
+//! - **Zero or one element**: 7 instructions
+//! - **All equal**: 13n - 5 instructions for n >= 2
+//! - **First mismatch at element i**: 13i - 3 to 13i + 6 instructions
+//! - **Memory ops**: 7 loads per equal element
+//!
+//! ## Algorithm
+//! 1. Compute the end pointer `keys + 32 * len` and start at element 1
+//! 2. Load element 0's first 8 bytes once
+//! 3. For each element, compare its four 8-byte chunks with element 0's and
+//!    return 0 on the first difference
+//! 4. Return 1 once the end is reached
+//!
+//! ## Register Usage
+//! - r0: Element 0's first 8 bytes, then the return value (1 = all equal,
+//!   0 = some element differs)
+//! - r1: Pointer to the current element (keys_ptr parameter)
+//! - r2: Element count (len parameter), then the end pointer
+//! - r3: Pointer to element 0
+//! - r4: Temporary for the current element's 8-byte chunk
+//! - r5: Temporary for element 0's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__all_equal
+.type __solana_pubkey_compare__all_equal, @function
+
+__solana_pubkey_compare__all_equal:
+    // Function parameters: r1 = keys_ptr, r2 = len
+    // Returns: r0 = 1 if every element equals the first, 0 otherwise
+
+    lsh64 r2, 5           // r2 = len * 32
+    add64 r2, r1          // r2 = end of the slice
+    mov64 r3, r1          // r3 = element 0
+    add64 r1, 32          // Start at element 1
+    jge r1, r2, equal     // Zero or one element
+    ldxdw r0, [r3+0]      // r0 = first 8 bytes of element 0
+
+element:
+    // Bytes 0-7
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of the element
+    jne r4, r0, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of the element
+    ldxdw r5, [r3+8]      // r5 = bytes 8-15 of element 0
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of the element
+    ldxdw r5, [r3+16]     // r5 = bytes 16-23 of element 0
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of the element
+    ldxdw r5, [r3+24]     // r5 = bytes 24-31 of element 0
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    add64 r1, 32          // Advance to the next element
+    jlt r1, r2, element   // Continue while elements remain
+
+equal:
+    // Every element matches - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // An element differs - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__all_equal, .-__solana_pubkey_compare__all_equal
@@ -0,0 +1,75 @@
+//! BPF assembly membership test of a key in a slice of keys
+//!
+//! Walks the slice in one routine rather than calling `fast_eq` per
+//! element. The needle's first 8 bytes stay in a register for the whole
+//! scan, so an element that differs there costs 4 instructions including
+//! the loop control; only first-word hits load the remaining 24 bytes.
+//!
+//! ## Performance Characteristics
+//! - **Empty slice**: 5 instructions
+//! - **Not found**: 4n + 6 instructions when no element shares the needle's
+//!   first 8 bytes
+//! - **Found at index i**: 4i + 17 instructions
+//! - **Memory ops**: 1 load per element, plus up to 6 per first-word hit
+//!
+//! ## Algorithm
+//! 1. Compute the end pointer as `haystack + 32 * len`
+//! 2. Load the needle's first 8 bytes once
+//! 3. For each element, compare its first 8 bytes with the needle's; on a
+//!    hit, compare the remaining chunks and return 1 if all match
+//! 4. Return 0 once the end pointer is reached
+//!
+//! ## Register Usage
+//! - r0: Needle's first 8 bytes, then the return value (1 = found, 0 = not)
+//! - r1: Pointer to the current element (haystack_ptr parameter)
+//! - r2: Element count (len parameter), then the end pointer
+//! - r3: Pointer to the needle (needle_ptr parameter)
+//! - r4: Temporary for the element's 8-byte chunk
+//! - r5: Temporary for the needle's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__contains
+.type __solana_pubkey_compare__contains, @function
+
+__solana_pubkey_compare__contains:
+    // Function parameters: r1 = haystack_ptr, r2 = len, r3 = needle_ptr
+    // Returns: r0 = 1 if the needle is in the haystack, 0 otherwise
+
+    lsh64 r2, 5           // r2 = len * 32
+    add64 r2, r1          // r2 = end of the haystack
+    jge r1, r2, absent    // Empty slice
+    ldxdw r0, [r3+0]      // r0 = first 8 bytes of the needle
+
+scan:
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of the element
+    jne r4, r0, next      // Most elements stop here
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of the element
+    ldxdw r5, [r3+8]      // r5 = bytes 8-15 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of the element
+    ldxdw r5, [r3+16]     // r5 = bytes 16-23 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of the element
+    ldxdw r5, [r3+24]     // r5 = bytes 24-31 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // All 32 bytes match - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+next:
+    add64 r1, 32          // Advance to the next element
+    jlt r1, r2, scan      // Continue while elements remain
+
+absent:
+    // No element matched - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__contains, .-__solana_pubkey_compare__contains
@@ -0,0 +1,78 @@
+//! BPF assembly count of a key's occurrences in a slice of keys
+//!
+//! The `contains` scan without the early exit. All six scratch registers
+//! are taken by the loop, so the count lives in a stack slot and is only
+//! touched on a full match; elements that differ in their first 8 bytes
+//! still cost 4 instructions each.
+//!
+//! ## Performance Characteristics
+//! - **Empty slice**: 6 instructions
+//! - **No matches**: 4n + 7 instructions when no element shares the needle's
+//!   first 8 bytes
+//! - **Each match**: 12 instructions more than a first-word miss
+//! - **Memory ops**: 1 load per element, plus up to 6 per first-word hit and
+//!   a stack load and store per match
+//!
+//! ## Algorithm
+//! 1. Zero the count and compute the end pointer `haystack + 32 * len`
+//! 2. Load the needle's first 8 bytes once
+//! 3. For each element, compare its first 8 bytes with the needle's; on a
+//!    hit, compare the remaining chunks and increment the count if all match
+//! 4. Return the count
+//!
+//! ## Register Usage
+//! - r0: Needle's first 8 bytes, then the return value (match count)
+//! - r1: Pointer to the current element (haystack_ptr parameter)
+//! - r2: Element count (len parameter), then the end pointer
+//! - r3: Pointer to the needle (needle_ptr parameter)
+//! - r4: Temporary for the element's 8-byte chunk, then the count
+//! - r5: Temporary for the needle's 8-byte chunk
+//! - r10: Frame pointer; [r10-8] holds the count
+
+.section .text
+.globl __solana_pubkey_compare__count_eq
+.type __solana_pubkey_compare__count_eq, @function
+
+__solana_pubkey_compare__count_eq:
+    // Function parameters: r1 = haystack_ptr, r2 = len, r3 = needle_ptr
+    // Returns: r0 = number of elements equal to the needle
+
+    stdw [r10-8], 0       // count = 0
+    lsh64 r2, 5           // r2 = len * 32
+    add64 r2, r1          // r2 = end of the haystack
+    jge r1, r2, done      // Empty slice
+    ldxdw r0, [r3+0]      // r0 = first 8 bytes of the needle
+
+scan:
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of the element
+    jne r4, r0, next      // Most elements stop here
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of the element
+    ldxdw r5, [r3+8]      // r5 = bytes 8-15 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of the element
+    ldxdw r5, [r3+16]     // r5 = bytes 16-23 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of the element
+    ldxdw r5, [r3+24]     // r5 = bytes 24-31 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // All 32 bytes match - count it
+    ldxdw r4, [r10-8]
+    add64 r4, 1
+    stxdw [r10-8], r4
+
+next:
+    add64 r1, 32          // Advance to the next element
+    jlt r1, r2, scan      // Continue while elements remain
+
+done:
+    ldxdw r0, [r10-8]     // r0 = count
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__count_eq, .-__solana_pubkey_compare__count_eq
@@ -0,0 +1,105 @@
+//! BPF assembly count of multisig members matched by provided signers
+//!
+//! For each member key, scans the array of signer key pointers until one
+//! matches, then moves on to the next member. Each member is counted at
+//! most once, so repeating a signer cannot inflate the tally towards a
+//! threshold. Five scratch registers hold the two loops, so the count, the
+//! start of the signer array and the end of the member array live in stack
+//! slots, and the member's first 8 bytes are reloaded after a partial match.
+//!
+//! ## Performance Characteristics
+//! - **Empty slices**: 9-10 instructions
+//! - **No matches**: 5mn + 5m + 11 instructions for m members and n signers
+//!   when no signer shares a member's first 8 bytes
+//! - **Each match**: 16 instructions for the matching signer, ending the
+//!   member's inner loop
+//! - **Memory ops**: 2 loads per signer visited, plus up to 6 per first-word
+//!   hit and a stack load and store per match
+//!
+//! ## Algorithm
+//! 1. Zero the count and compute the ends of both arrays
+//! 2. For each member, load its first 8 bytes and restart at the first
+//!    signer
+//! 3. For each signer, load its key pointer and compare the first 8 bytes;
+//!    on a hit, compare the remaining chunks, and on a full match increment
+//!    the count and move to the next member
+//! 4. Return the count
+//!
+//! ## Register Usage
+//! - r0: Member's first 8 bytes, or its current chunk during a partial
+//!   match, then the return value (matched member count)
+//! - r1: Pointer to the current signer entry (signers_ptr parameter)
+//! - r2: Signer count (signers_len parameter), then the end of the signers
+//! - r3: Pointer to the current member (members_ptr parameter)
+//! - r4: Member count (members_len parameter), then the end of the members,
+//!   then a temporary for the signer's 8-byte chunk
+//! - r5: Pointer to the current signer's key
+//! - r10: Frame pointer; [r10-8] holds the count, [r10-16] the start of the
+//!   signers and [r10-24] the end of the members
+
+.section .text
+.globl __solana_pubkey_compare__count_members
+.type __solana_pubkey_compare__count_members, @function
+
+__solana_pubkey_compare__count_members:
+    // Function parameters: r1 = signers_ptr, r2 = signers_len,
+    //                      r3 = members_ptr, r4 = members_len
+    // Returns: r0 = number of members equal to some signer
+
+    stdw [r10-8], 0       // count = 0
+    stxdw [r10-16], r1    // Save the start of the signers
+    lsh64 r2, 3           // r2 = signers_len * 8
+    add64 r2, r1          // r2 = end of the signers
+    lsh64 r4, 5           // r4 = members_len * 32
+    add64 r4, r3          // r4 = end of the members
+    jge r3, r4, done      // No members
+    jge r1, r2, done      // No signers
+    stxdw [r10-24], r4    // Save the end of the members
+
+member:
+    ldxdw r0, [r3+0]      // r0 = first 8 bytes of the member
+    ldxdw r1, [r10-16]    // Restart at the first signer
+
+signer:
+    ldxdw r5, [r1+0]      // r5 = pointer to the signer's key
+    ldxdw r4, [r5+0]      // r4 = first 8 bytes of the signer
+    jne r4, r0, next      // Most signers stop here
+
+    // Bytes 8-15
+    ldxdw r4, [r5+8]      // r4 = bytes 8-15 of the signer
+    ldxdw r0, [r3+8]      // r0 = bytes 8-15 of the member
+    jne r4, r0, reload    // Move on if chunks differ
+
+    // Bytes 16-23
+    ldxdw r4, [r5+16]     // r4 = bytes 16-23 of the signer
+    ldxdw r0, [r3+16]     // r0 = bytes 16-23 of the member
+    jne r4, r0, reload    // Move on if chunks differ
+
+    // Bytes 24-31
+    ldxdw r4, [r5+24]     // r4 = bytes 24-31 of the signer
+    ldxdw r0, [r3+24]     // r0 = bytes 24-31 of the member
+    jne r4, r0, reload    // Move on if chunks differ
+
+    // All 32 bytes match - count the member once
+    ldxdw r4, [r10-8]
+    add64 r4, 1
+    stxdw [r10-8], r4
+    ja next_member
+
+reload:
+    ldxdw r0, [r3+0]      // Restore the member's first 8 bytes
+
+next:
+    add64 r1, 8           // Advance to the next signer entry
+    jlt r1, r2, signer    // Continue while signers remain
+
+next_member:
+    ldxdw r4, [r10-24]    // r4 = end of the members
+    add64 r3, 32          // Advance to the next member
+    jlt r3, r4, member    // Continue while members remain
+
+done:
+    ldxdw r0, [r10-8]     // r0 = count
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__count_members, .-__solana_pubkey_compare__count_members
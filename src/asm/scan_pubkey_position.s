@@ -0,0 +1,79 @@
+//! BPF assembly index search for a key in a slice of keys
+//!
+//! The `contains` scan, returning where the needle was found. The index is
+//! recovered from the element pointer once, after the loop, so the per
+//! element cost stays at 4 instructions: no counter is kept. The slice start
+//! is spilled to the stack because the loop needs every scratch register.
+//!
+//! ## Performance Characteristics
+//! - **Empty slice**: 9 instructions
+//! - **Not found**: 4n + 11 instructions when no element shares the needle's
+//!   first 8 bytes
+//! - **Found at index i**: 4i + 21 instructions
+//! - **Memory ops**: 1 load per element, plus up to 6 per first-word hit, and
+//!   one stack store and load
+//!
+//! ## Algorithm
+//! 1. Save the slice start and compute the end pointer `haystack + 32 * len`
+//! 2. Load the needle's first 8 bytes once
+//! 3. For each element, compare its first 8 bytes with the needle's; on a
+//!    hit, compare the remaining chunks and stop if all match
+//! 4. Return `(element - start) / 32`, which is `len` if nothing matched
+//!
+//! ## Register Usage
+//! - r0: Needle's first 8 bytes, then the return value (index, or len)
+//! - r1: Pointer to the current element (haystack_ptr parameter)
+//! - r2: Element count (len parameter), then the end pointer, then the
+//!   slice start
+//! - r3: Pointer to the needle (needle_ptr parameter)
+//! - r4: Temporary for the element's 8-byte chunk
+//! - r5: Temporary for the needle's 8-byte chunk
+//! - r10: Frame pointer; [r10-8] holds the slice start
+
+.section .text
+.globl __solana_pubkey_compare__position_of
+.type __solana_pubkey_compare__position_of, @function
+
+__solana_pubkey_compare__position_of:
+    // Function parameters: r1 = haystack_ptr, r2 = len, r3 = needle_ptr
+    // Returns: r0 = index of the first match, or len if there is none
+
+    stxdw [r10-8], r1     // Save the slice start for the index
+    lsh64 r2, 5           // r2 = len * 32
+    add64 r2, r1          // r2 = end of the haystack
+    jge r1, r2, done      // Empty slice
+    ldxdw r0, [r3+0]      // r0 = first 8 bytes of the needle
+
+scan:
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of the element
+    jne r4, r0, next      // Most elements stop here
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of the element
+    ldxdw r5, [r3+8]      // r5 = bytes 8-15 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of the element
+    ldxdw r5, [r3+16]     // r5 = bytes 16-23 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of the element
+    ldxdw r5, [r3+24]     // r5 = bytes 24-31 of the needle
+    jne r4, r5, next      // Move on if chunks differ
+
+done:
+    // r1 points at the match, or at the end of the slice
+    ldxdw r2, [r10-8]     // r2 = slice start
+    mov64 r0, r1
+    sub64 r0, r2          // r0 = byte offset of r1
+    rsh64 r0, 5           // r0 = element index
+    exit                  // Return to caller
+
+next:
+    add64 r1, 32          // Advance to the next element
+    jlt r1, r2, scan      // Continue while elements remain
+    ja done               // No element matched
+
+.size __solana_pubkey_compare__position_of, .-__solana_pubkey_compare__position_of
@@ -0,0 +1,74 @@
+//! BPF assembly element-wise equality of two equal-length key slices
+//!
+//! Walks both slices in one routine with the `fast_eq` early-exit chain per
+//! element, instead of a `fast_eq` call per pair. The caller has already
+//! checked that the lengths match.
+//!
+//! ## Performance Characteristics
+//! - **Empty slices**: 5 instructions
+//! - **Equal slices**: 15n + 5 instructions
+//! - **First mismatch at element i**: 15i + 8 to 15i + 17 instructions
+//! - **Memory ops**: 8 loads per equal element
+//!
+//! ## Algorithm
+//! 1. Compute the end of `lhs` as `lhs + 32 * len`
+//! 2. For each element pair, compare the four 8-byte chunks and return 0 on
+//!    the first difference
+//! 3. Return 1 once the end is reached
+//!
+//! ## Register Usage
+//! - r0: Return value (1 = slices equal, 0 = some element differs)
+//! - r1: Pointer to the current `lhs` element (lhs_ptr parameter)
+//! - r2: Element count (len parameter), then the end of `lhs`
+//! - r3: Pointer to the current `rhs` element (rhs_ptr parameter)
+//! - r4: Temporary for the `lhs` element's 8-byte chunk
+//! - r5: Temporary for the `rhs` element's 8-byte chunk
+
+.section .text
+.globl __solana_pubkey_compare__slices_eq
+.type __solana_pubkey_compare__slices_eq, @function
+
+__solana_pubkey_compare__slices_eq:
+    // Function parameters: r1 = lhs_ptr, r2 = len, r3 = rhs_ptr
+    // Returns: r0 = 1 if every element pair is equal, 0 otherwise
+
+    lsh64 r2, 5           // r2 = len * 32
+    add64 r2, r1          // r2 = end of lhs
+    jge r1, r2, equal     // Empty slices
+
+pair:
+    // Bytes 0-7
+    ldxdw r4, [r1+0]      // r4 = first 8 bytes of the lhs element
+    ldxdw r5, [r3+0]      // r5 = first 8 bytes of the rhs element
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 8-15
+    ldxdw r4, [r1+8]      // r4 = bytes 8-15 of the lhs element
+    ldxdw r5, [r3+8]      // r5 = bytes 8-15 of the rhs element
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 16-23
+    ldxdw r4, [r1+16]     // r4 = bytes 16-23 of the lhs element
+    ldxdw r5, [r3+16]     // r5 = bytes 16-23 of the rhs element
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    // Bytes 24-31
+    ldxdw r4, [r1+24]     // r4 = bytes 24-31 of the lhs element
+    ldxdw r5, [r3+24]     // r5 = bytes 24-31 of the rhs element
+    jne r4, r5, not_equal // Early exit if chunks differ
+
+    add64 r1, 32          // Advance lhs to the next element
+    add64 r3, 32          // Advance rhs to the next element
+    jlt r1, r2, pair      // Continue while elements remain
+
+equal:
+    // Every pair matches - return true
+    mov64 r0, 1
+    exit                  // Return to caller
+
+not_equal:
+    // An element differs - return false
+    mov64 r0, 0
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__slices_eq, .-__solana_pubkey_compare__slices_eq
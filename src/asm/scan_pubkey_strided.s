@@ -0,0 +1,87 @@
+//! BPF assembly search for a key field in fixed-size records
+//!
+//! Backs `scan_keys`: record `i` has its 32-byte key at `base + i * stride`.
+//! `find_key_in_bytes` runs it with a stride of 1 to try every byte offset.
+//! As in `position_of`, the needle's first 8 bytes stay in a register and
+//! the record index is recovered from the pointer after the loop, so a
+//! record whose key differs in its first 8 bytes costs 4 instructions.
+//! With the stride occupying a register, r0 doubles as the needle
+//! temporary on first-word hits and is reloaded if the hit fails.
+//!
+//! ## Performance Characteristics
+//! - **No records**: 9 instructions
+//! - **Not found**: 4n + 11 instructions when no record shares the needle's
+//!   first 8 bytes
+//! - **Found at index i**: 4i + 21 instructions
+//! - **Memory ops**: 1 load per record, plus up to 7 per first-word hit, and
+//!   one stack store and load
+//!
+//! ## Algorithm
+//! 1. Save the first record's key address and compute the end pointer
+//!    `base + count * stride`
+//! 2. Load the needle's first 8 bytes once
+//! 3. For each record, compare its key's first 8 bytes with the needle's; on
+//!    a hit, compare the remaining chunks and stop if all match
+//! 4. Return `(pointer - base) / stride`, which is `count` if nothing matched
+//!
+//! ## Register Usage
+//! - r0: Needle's first 8 bytes (also the needle temporary on first-word
+//!   hits), then the return value (index, or count)
+//! - r1: Pointer to the current record's key (base_ptr parameter)
+//! - r2: Record count (count parameter), then the end pointer, then the base
+//! - r3: Distance between records in bytes (stride parameter)
+//! - r4: Pointer to the needle (needle_ptr parameter)
+//! - r5: Temporary for the record key's 8-byte chunk
+//! - r10: Frame pointer; [r10-8] holds the base
+
+.section .text
+.globl __solana_pubkey_compare__scan_strided
+.type __solana_pubkey_compare__scan_strided, @function
+
+__solana_pubkey_compare__scan_strided:
+    // Function parameters: r1 = base_ptr, r2 = count, r3 = stride,
+    // r4 = needle_ptr
+    // Returns: r0 = index of the first matching record, or count
+
+    stxdw [r10-8], r1     // Save the base for the index
+    mul64 r2, r3          // r2 = count * stride
+    add64 r2, r1          // r2 = one stride past the last record
+    jge r1, r2, done      // No records
+    ldxdw r0, [r4+0]      // r0 = first 8 bytes of the needle
+
+scan:
+    ldxdw r5, [r1+0]      // r5 = first 8 bytes of the record key
+    jne r5, r0, next      // Most records stop here
+
+    // Bytes 8-15
+    ldxdw r5, [r1+8]      // r5 = bytes 8-15 of the record key
+    ldxdw r0, [r4+8]      // r0 = bytes 8-15 of the needle
+    jne r5, r0, restore   // Move on if chunks differ
+
+    // Bytes 16-23
+    ldxdw r5, [r1+16]     // r5 = bytes 16-23 of the record key
+    ldxdw r0, [r4+16]     // r0 = bytes 16-23 of the needle
+    jne r5, r0, restore   // Move on if chunks differ
+
+    // Bytes 24-31
+    ldxdw r5, [r1+24]     // r5 = bytes 24-31 of the record key
+    ldxdw r0, [r4+24]     // r0 = bytes 24-31 of the needle
+    jne r5, r0, restore   // Move on if chunks differ
+
+done:
+    // r1 points at the match, or one stride past the last record
+    ldxdw r2, [r10-8]     // r2 = base
+    mov64 r0, r1
+    sub64 r0, r2          // r0 = byte offset of r1
+    div64 r0, r3          // r0 = record index
+    exit                  // Return to caller
+
+restore:
+    ldxdw r0, [r4+0]      // r0 = first 8 bytes of the needle again
+
+next:
+    add64 r1, r3          // Advance to the next record
+    jlt r1, r2, scan      // Continue while records remain
+    ja done               // No record matched
+
+.size __solana_pubkey_compare__scan_strided, .-__solana_pubkey_compare__scan_strided
@@ -0,0 +1,79 @@
+//! BPF assembly batch check of provided keys against expected keys
+//!
+//! Compares `expected[i]` with `*actual[i]` for every `i` in one routine,
+//! where `actual` is an array of key pointers as held by account lists.
+//! Returns the first mismatching index, recovered from the element pointer
+//! after the loop as in `position_of`, so no counter is kept.
+//!
+//! ## Performance Characteristics
+//! - **Empty slices**: 9 instructions
+//! - **All match**: 16n + 9 instructions
+//! - **First mismatch at index i**: 16i + 13 to 16i + 22 instructions
+//! - **Memory ops**: 9 loads per matching pair, plus one stack store and load
+//!
+//! ## Algorithm
+//! 1. Save the start of `expected` and compute its end `expected + 32 * len`
+//! 2. For each index, load the `actual` pointer, compare the four 8-byte
+//!    chunks and stop on the first difference
+//! 3. Return `(element - start) / 32`, which is `len` if every pair matched
+//!
+//! ## Register Usage
+//! - r0: Temporary for the provided key's 8-byte chunk, then the return
+//!   value (first mismatching index, or len)
+//! - r1: Pointer to the current expected key (expected_ptr parameter)
+//! - r2: Pair count (len parameter), then the end of `expected`, then its
+//!   start
+//! - r3: Pointer to the current `actual` entry (actual_ptr parameter)
+//! - r4: Pointer to the current provided key
+//! - r5: Temporary for the expected key's 8-byte chunk
+//! - r10: Frame pointer; [r10-8] holds the start of `expected`
+
+.section .text
+.globl __solana_pubkey_compare__validate_keys
+.type __solana_pubkey_compare__validate_keys, @function
+
+__solana_pubkey_compare__validate_keys:
+    // Function parameters: r1 = expected_ptr, r2 = len, r3 = actual_ptr
+    // Returns: r0 = index of the first mismatching pair, or len
+
+    stxdw [r10-8], r1     // Save the start of expected for the index
+    lsh64 r2, 5           // r2 = len * 32
+    add64 r2, r1          // r2 = end of expected
+    jge r1, r2, done      // Empty slices
+
+pair:
+    ldxdw r4, [r3+0]      // r4 = pointer to the provided key
+
+    // Bytes 0-7
+    ldxdw r5, [r1+0]      // r5 = first 8 bytes of the expected key
+    ldxdw r0, [r4+0]      // r0 = first 8 bytes of the provided key
+    jne r5, r0, done      // Stop at the first difference
+
+    // Bytes 8-15
+    ldxdw r5, [r1+8]      // r5 = bytes 8-15 of the expected key
+    ldxdw r0, [r4+8]      // r0 = bytes 8-15 of the provided key
+    jne r5, r0, done      // Stop at the first difference
+
+    // Bytes 16-23
+    ldxdw r5, [r1+16]     // r5 = bytes 16-23 of the expected key
+    ldxdw r0, [r4+16]     // r0 = bytes 16-23 of the provided key
+    jne r5, r0, done      // Stop at the first difference
+
+    // Bytes 24-31
+    ldxdw r5, [r1+24]     // r5 = bytes 24-31 of the expected key
+    ldxdw r0, [r4+24]     // r0 = bytes 24-31 of the provided key
+    jne r5, r0, done      // Stop at the first difference
+
+    add64 r1, 32          // Advance to the next expected key
+    add64 r3, 8           // Advance to the next provided key pointer
+    jlt r1, r2, pair      // Continue while pairs remain
+
+done:
+    // r1 points at the mismatching expected key, or at the end
+    ldxdw r2, [r10-8]     // r2 = start of expected
+    mov64 r0, r1
+    sub64 r0, r2          // r0 = byte offset of r1
+    rsh64 r0, 5           // r0 = index
+    exit                  // Return to caller
+
+.size __solana_pubkey_compare__validate_keys, .-__solana_pubkey_compare__validate_keys
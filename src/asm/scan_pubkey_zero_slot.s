@@ -0,0 +1,68 @@
+//! BPF assembly search for the first all-zero entry in a packed key array
+//!
+//! Registries mark free slots with `Pubkey::default()`. This is the
+//! `fast_is_zero` chunk test run over the array in one loop: each chunk is
+//! compared against an immediate zero, so no zero key is materialized, and
+//! the index is recovered from the entry pointer after the loop as in
+//! `position_of`. An occupied entry with a set byte in its first 8 bytes
+//! costs 4 instructions including loop control.
+//!
+//! ## Performance Characteristics
+//! - **Empty array**: 8 instructions
+//! - **No free slot**: 4n + 9 instructions when every entry has a set byte
+//!   in its first 8 bytes
+//! - **Free slot at index i**: 4i + 16 instructions
+//! - **Memory ops**: 1 load per entry, plus up to 3 per zero first chunk
+//!
+//! ## Algorithm
+//! 1. Save the array start and compute the end pointer `data + 32 * count`
+//! 2. For each entry, test the four 8-byte chunks against zero and stop at
+//!    the first entry where all are zero
+//! 3. Return `(entry - start) / 32`, which is `count` if no entry is zero
+//!
+//! ## Register Usage
+//! - r0: Return value (index of the first zero entry, or count)
+//! - r1: Pointer to the current entry (data_ptr parameter)
+//! - r2: Entry count (count parameter), then the end pointer
+//! - r3: Temporary for the entry's 8-byte chunk
+//! - r4: Array start
+
+.section .text
+.globl __solana_pubkey_compare__find_zero_slot
+.type __solana_pubkey_compare__find_zero_slot, @function
+
+__solana_pubkey_compare__find_zero_slot:
+    // Function parameters: r1 = data_ptr, r2 = count
+    // Returns: r0 = index of the first all-zero entry, or count
+
+    mov64 r4, r1          // Save the array start for the index
+    lsh64 r2, 5           // r2 = count * 32
+    add64 r2, r1          // r2 = end of the array
+    jge r1, r2, done      // Empty array
+
+scan:
+    ldxdw r3, [r1+0]      // r3 = bytes 0-7 of the entry
+    jne r3, 0, next       // Most occupied entries stop here
+
+    ldxdw r3, [r1+8]      // r3 = bytes 8-15 of the entry
+    jne r3, 0, next       // Move on at a set byte
+
+    ldxdw r3, [r1+16]     // r3 = bytes 16-23 of the entry
+    jne r3, 0, next       // Move on at a set byte
+
+    ldxdw r3, [r1+24]     // r3 = bytes 24-31 of the entry
+    jne r3, 0, next       // Move on at a set byte
+
+done:
+    // r1 points at the zero entry, or at the end of the array
+    mov64 r0, r1
+    sub64 r0, r4          // r0 = byte offset of r1
+    rsh64 r0, 5           // r0 = entry index
+    exit                  // Return to caller
+
+next:
+    add64 r1, 32          // Advance to the next entry
+    jlt r1, r2, scan      // Continue while entries remain
+    ja done               // No entry was zero
+
+.size __solana_pubkey_compare__find_zero_slot, .-__solana_pubkey_compare__find_zero_slot
@@ -0,0 +1,50 @@
+// Assembly routines linked into BPF builds, shared by `build.rs` and the
+// assembly budget test. Each entry names the cargo feature that links it, or
+// `None` for routines every configuration needs. With `opt-size`, a source
+// is replaced by its namesake in `OPT_SIZE_DIR` where one exists.
+const ASM_SOURCES: &[(Option<&str>, &str)] = &[
+    (None, "src/asm/cmp_pubkey_eq.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_eq_branchless.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_ne.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_eq_rev.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_eq_ptrcheck.s"),
+    (Some("cmp"), "src/asm/assert_pubkey_eq.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_cmp.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_is_zero.s"),
+    (Some("cmp"), "src/asm/cmp_eq_64.s"),
+    (Some("cmp"), "src/asm/cmp_eq_8.s"),
+    (Some("cmp"), "src/asm/cmp_eq_16.s"),
+    (Some("cmp"), "src/asm/cmp_eq_128.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_eq_either.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_eq_pairs.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_prefix_eq.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_masked_eq.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_diff_index.s"),
+    (Some("cmp"), "src/asm/cmp_pubkey_first_diff_qword.s"),
+    (Some("scan"), "src/asm/scan_pubkey_contains.s"),
+    (Some("scan"), "src/asm/scan_pubkey_position.s"),
+    (Some("scan"), "src/asm/scan_pubkey_count.s"),
+    (Some("scan"), "src/asm/scan_pubkey_strided.s"),
+    (Some("scan"), "src/asm/scan_pubkey_slices_eq.s"),
+    (Some("scan"), "src/asm/scan_pubkey_all_equal.s"),
+    (Some("scan"), "src/asm/scan_pubkey_validate.s"),
+    (Some("scan"), "src/asm/scan_pubkey_count_members.s"),
+    (Some("scan"), "src/asm/scan_pubkey_zero_slot.s"),
+    (Some("copy"), "src/asm/key_cas.s"),
+];
+
+/// Directory holding size-optimized replacements, used with `opt-size`
+const OPT_SIZE_DIR: &str = "src/asm/opt-size";
+
+/// Returns the source to assemble for `source`, preferring its
+/// size-optimized replacement when the `opt-size` feature is enabled
+fn select_source(source: &str, opt_size: bool) -> String {
+    if opt_size {
+        let file_name = std::path::Path::new(source).file_name().unwrap().to_str().unwrap();
+        let replacement = format!("{OPT_SIZE_DIR}/{file_name}");
+        if std::path::Path::new(&replacement).exists() {
+            return replacement;
+        }
+    }
+    source.to_string()
+}
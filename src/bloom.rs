@@ -0,0 +1,131 @@
+//! Bloom filters over keys, with bit positions from the key [`fingerprint`]
+//!
+//! [`PubkeyBloom`] needs no hashing syscall: one fingerprint yields all four
+//! bit positions, at about 60 instructions per insert or query on Solana.
+
+use crate::{Key, fingerprint};
+
+/// A Bloom filter of `8 * BYTES` bits answering "possibly contains" queries
+///
+/// Each key sets four bits, derived by double hashing from the two halves of
+/// its [`fingerprint`]: position `i` is `h1 + i * h2` modulo the bit count.
+/// Every byte of the key feeds every position, so keys with shared prefixes,
+/// such as sysvar ids and vanity addresses, spread like random ones.
+///
+/// Queries have no false negatives. With `n` keys inserted into `m` bits,
+/// the false-positive rate is about `(1 - e^(-4n/m))^4`: 1% at roughly 10
+/// bits per key and 0.1% at roughly 16. A positive answer must be confirmed
+/// with an exact check, such as a search of the full list, so the filter
+/// suits large allowlists where most queries miss.
+///
+/// The filter is a plain byte array with alignment 1, so
+/// [`PubkeyBloom::from_bytes_mut`] can view account data at any offset in
+/// place.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::PubkeyBloom;
+///
+/// let mut allowlist = PubkeyBloom::<128>::new();
+/// allowlist.insert(&[1u8; 32]);
+///
+/// assert!(allowlist.maybe_contains(&[1u8; 32]));
+/// // Usually false; a `true` here would be a false positive
+/// let _ = allowlist.maybe_contains(&[2u8; 32]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+pub struct PubkeyBloom<const BYTES: usize> {
+    bits: [u8; BYTES],
+}
+
+impl<const BYTES: usize> PubkeyBloom<BYTES> {
+    /// Number of bits in the filter
+    pub const BITS: usize = 8 * BYTES;
+
+    /// Size of the encoded filter in bytes
+    pub const SIZE: usize = BYTES;
+
+    /// Creates an empty filter
+    pub const fn new() -> Self {
+        PubkeyBloom { bits: [0; BYTES] }
+    }
+
+    /// Views the first [`PubkeyBloom::SIZE`] bytes of `data` as a filter
+    #[inline]
+    pub fn from_bytes(data: &[u8]) -> Option<&Self> {
+        let data = data.get(..Self::SIZE)?;
+        // SAFETY: `PubkeyBloom` is `repr(C)` over a byte array only, so it
+        // has alignment 1, no padding, and every bit pattern is valid.
+        Some(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    /// Views the first [`PubkeyBloom::SIZE`] bytes of `data` as a mutable
+    /// filter
+    #[inline]
+    pub fn from_bytes_mut(data: &mut [u8]) -> Option<&mut Self> {
+        let data = data.get_mut(..Self::SIZE)?;
+        // SAFETY: as in `from_bytes`.
+        Some(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    /// Adds `key` to the filter
+    #[inline]
+    pub fn insert<K: Key>(&mut self, key: &K) {
+        for bit in positions::<BYTES>(key.key_bytes()) {
+            self.bits[bit / 8] |= 1 << (bit % 8);
+        }
+    }
+
+    /// Returns `false` if `key` was certainly never inserted, and `true` if
+    /// it may have been
+    #[inline]
+    pub fn maybe_contains<K: Key>(&self, key: &K) -> bool {
+        positions::<BYTES>(key.key_bytes())
+            .into_iter()
+            .all(|bit| self.bits[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+
+    /// Returns the number of set bits, for estimating how full the filter is
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum()
+    }
+
+    /// Removes every key
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bits = [0; BYTES];
+    }
+}
+
+impl<const BYTES: usize> Default for PubkeyBloom<BYTES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BYTES: usize> core::fmt::Debug for PubkeyBloom<BYTES> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PubkeyBloom")
+            .field("bits", &Self::BITS)
+            .field("set", &self.count_ones())
+            .finish()
+    }
+}
+
+/// The four bit positions of `key`, by double hashing over the halves of
+/// its fingerprint
+#[inline(always)]
+fn positions<const BYTES: usize>(key: &[u8; 32]) -> [usize; 4] {
+    const { assert!(BYTES > 0, "PubkeyBloom must have at least one byte") };
+    let bits = 8 * BYTES as u64;
+    let fingerprint = fingerprint(key);
+    // An odd stride keeps the four positions distinct in power-of-two filters
+    let (h1, h2) = (fingerprint & 0xFFFF_FFFF, (fingerprint >> 32) | 1);
+    [0, 1, 2, 3].map(|i| ((h1 + i * h2) % bits) as usize)
+}
@@ -0,0 +1,66 @@
+//! Compute-unit costs of the comparison routines
+//!
+//! By default these are instruction counts of the assembly in this
+//! configuration (sBPF charges one unit per instruction), excluding the call
+//! and argument setup around it. With the `measured-costs` feature they are
+//! instead generated from a run of the bench program under mollusk, so they
+//! include that overhead and track the pinned runtime exactly:
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path bench/Cargo.toml
+//! cargo run --manifest-path bench/mollusk/Cargo.toml -- costs_measured.rs
+//! SOLANA_PUBKEY_COMPARE_COSTS=$PWD/costs_measured.rs cargo build --features measured-costs
+//! ```
+
+/// Best- and worst-case compute units of a routine over all inputs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoutineCost {
+    /// Cheapest input, e.g. keys differing in their first word
+    pub best: u64,
+    /// Most expensive input, e.g. equal keys
+    pub worst: u64,
+}
+
+#[cfg(not(feature = "measured-costs"))]
+mod model {
+    use super::RoutineCost;
+
+    /// Early-exit [`fast_eq`](crate::fast_eq): one compare per word until the
+    /// first mismatch
+    #[cfg(not(feature = "opt-size"))]
+    pub const FAST_EQ_EARLY_EXIT: RoutineCost = RoutineCost { best: 5, worst: 14 };
+    /// Early-exit [`fast_eq`](crate::fast_eq), loop encoding
+    #[cfg(feature = "opt-size")]
+    pub const FAST_EQ_EARLY_EXIT: RoutineCost = RoutineCost { best: 6, worst: 31 };
+
+    /// [`fast_eq_branchless`](crate::fast_eq_branchless): fixed cost
+    #[cfg(not(feature = "opt-size"))]
+    pub const FAST_EQ_BRANCHLESS: RoutineCost = RoutineCost {
+        best: 21,
+        worst: 21,
+    };
+    /// [`fast_eq_branchless`](crate::fast_eq_branchless), loop encoding
+    #[cfg(feature = "opt-size")]
+    pub const FAST_EQ_BRANCHLESS: RoutineCost = RoutineCost {
+        best: 40,
+        worst: 40,
+    };
+}
+
+#[cfg(feature = "measured-costs")]
+mod model {
+    use super::RoutineCost;
+
+    include!(concat!(env!("OUT_DIR"), "/costs_measured.rs"));
+}
+
+pub use model::{FAST_EQ_BRANCHLESS, FAST_EQ_EARLY_EXIT};
+
+/// Cost of [`fast_eq`](crate::fast_eq) as configured by the `branchless`
+/// feature
+#[cfg(not(feature = "branchless"))]
+pub const FAST_EQ: RoutineCost = FAST_EQ_EARLY_EXIT;
+/// Cost of [`fast_eq`](crate::fast_eq) as configured by the `branchless`
+/// feature
+#[cfg(feature = "branchless")]
+pub const FAST_EQ: RoutineCost = FAST_EQ_BRANCHLESS;
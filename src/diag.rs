@@ -0,0 +1,37 @@
+//! Failure diagnostics for the validation helpers
+//!
+//! With the `debug-logs` feature, a failed check logs its name, the account
+//! index involved (if any) and a hex dump of each key via `sol_log`. Without
+//! the feature, [`log_failure`] is an empty inline function and compiles to
+//! nothing, keeping production builds at minimal CU and size.
+
+#[cfg(feature = "debug-logs")]
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+/// Logs a failed check named `check`, with an optional account index and
+/// the keys it compared
+#[cfg(feature = "debug-logs")]
+#[cold]
+#[inline(never)]
+pub(crate) fn log_failure(check: &str, index: Option<usize>, keys: &[&[u8; 32]]) {
+    use solana_program::log::{sol_log, sol_log_64};
+
+    sol_log(check);
+    if let Some(index) = index {
+        sol_log_64(index as u64, 0, 0, 0, 0);
+    }
+    for key in keys {
+        let mut hex = [0u8; 64];
+        for (byte, out) in key.iter().zip(hex.chunks_exact_mut(2)) {
+            out[0] = HEX[(byte >> 4) as usize];
+            out[1] = HEX[(byte & 0xf) as usize];
+        }
+        // SAFETY: every byte written above is an ASCII hex digit.
+        sol_log(unsafe { core::str::from_utf8_unchecked(&hex) });
+    }
+}
+
+/// No-op without the `debug-logs` feature
+#[cfg(not(feature = "debug-logs"))]
+#[inline(always)]
+pub(crate) fn log_failure(_check: &str, _index: Option<usize>, _keys: &[&[u8; 32]]) {}
@@ -0,0 +1,88 @@
+//! Key set digests for off-chain conflict prescreening
+//!
+//! Batchers and schedulers test many transaction pairs for account-lock
+//! conflicts. A [`KeySetDigest`] reduces a writable key set to sorted 32-bit
+//! fingerprints, so most non-conflicting pairs are ruled out without
+//! touching the keys; only pairs that may overlap need an exact check.
+
+use crate::{Key, fingerprint};
+
+/// Compact digest of a key set, answering "possibly overlaps" queries
+///
+/// Holds one 32-bit fingerprint per distinct key: the high half of its
+/// [`fingerprint`], which every byte of the key feeds. Queries have no false
+/// negatives: keys in both sets always report an overlap. False positives
+/// need a fingerprint collision, about `n * m / 2^32` for sets of `n` and `m`
+/// keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::KeySetDigest;
+///
+/// let a = KeySetDigest::from_keys(&[[1u8; 32], [2u8; 32]]);
+/// let b = KeySetDigest::from_keys(&[[2u8; 32], [3u8; 32]]);
+/// let c = KeySetDigest::from_keys(&[[4u8; 32]]);
+///
+/// assert!(a.may_overlap(&b));
+/// assert!(!a.may_overlap(&c));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeySetDigest {
+    fingerprints: Vec<u32>,
+}
+
+impl KeySetDigest {
+    /// Builds the digest of `keys`; order and repeated keys do not matter
+    pub fn from_keys<K: Key>(keys: &[K]) -> Self {
+        let mut fingerprints: Vec<u32> = keys
+            .iter()
+            .map(|key| short_fingerprint(key.key_bytes()))
+            .collect();
+        fingerprints.sort_unstable();
+        fingerprints.dedup();
+        Self { fingerprints }
+    }
+
+    /// Number of distinct fingerprints
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fingerprints.len()
+    }
+
+    /// Returns `true` if the digest was built from no keys
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fingerprints.is_empty()
+    }
+
+    /// Returns `true` if `key` may be in the set
+    #[inline]
+    pub fn may_contain<K: Key>(&self, key: &K) -> bool {
+        self.fingerprints
+            .binary_search(&short_fingerprint(key.key_bytes()))
+            .is_ok()
+    }
+
+    /// Returns `true` if the two sets may share a key
+    ///
+    /// Merges the sorted fingerprints in `O(n + m)`.
+    pub fn may_overlap(&self, other: &Self) -> bool {
+        let (a, b) = (&self.fingerprints, &other.fingerprints);
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                core::cmp::Ordering::Less => i += 1,
+                core::cmp::Ordering::Greater => j += 1,
+                core::cmp::Ordering::Equal => return true,
+            }
+        }
+        false
+    }
+}
+
+/// High 32 bits of a key's [`fingerprint`]
+#[inline(always)]
+fn short_fingerprint(key: &[u8; 32]) -> u32 {
+    (fingerprint(key) >> 32) as u32
+}
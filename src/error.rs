@@ -0,0 +1,91 @@
+//! Crate-wide error type for validation and collection APIs
+
+use crate::parse::ParseError;
+
+/// Base of the custom program error codes produced from [`CompareError`]
+///
+/// Chosen well above Anchor's 6000 offset and the small codes native programs
+/// typically use, so the two ranges do not collide.
+pub const ERROR_CODE_BASE: u32 = 0x504B_0000;
+
+/// Why a key validation or collection operation failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompareError {
+    /// The key at `index` did not match its expected value
+    KeyMismatch {
+        /// Position of the first mismatching key
+        index: usize,
+    },
+    /// An account is not owned by the expected program
+    OwnerMismatch,
+    /// A buffer or key list has an invalid length or layout
+    LengthInvalid,
+    /// The requested key is not present
+    NotFound,
+    /// The same key occurs at two positions that must be distinct
+    DuplicateKey {
+        /// Position of the first occurrence
+        i: usize,
+        /// Position of the repeated occurrence
+        j: usize,
+    },
+    /// A fixed-capacity key region has no room for another key
+    RegionFull,
+}
+
+impl CompareError {
+    /// Returns the custom program error code for this error
+    ///
+    /// Each variant maps to `ERROR_CODE_BASE` plus its declaration order;
+    /// positions are only reported through `Debug`/`Display`.
+    #[inline]
+    pub const fn code(&self) -> u32 {
+        ERROR_CODE_BASE
+            + match self {
+                CompareError::KeyMismatch { .. } => 0,
+                CompareError::OwnerMismatch => 1,
+                CompareError::LengthInvalid => 2,
+                CompareError::NotFound => 3,
+                CompareError::DuplicateKey { .. } => 4,
+                CompareError::RegionFull => 5,
+            }
+    }
+}
+
+impl From<ParseError> for CompareError {
+    fn from(_: ParseError) -> Self {
+        CompareError::LengthInvalid
+    }
+}
+
+impl From<CompareError> for u32 {
+    fn from(error: CompareError) -> Self {
+        error.code()
+    }
+}
+
+#[cfg(feature = "solana-program")]
+impl From<CompareError> for solana_program::program_error::ProgramError {
+    fn from(error: CompareError) -> Self {
+        solana_program::program_error::ProgramError::Custom(error.code())
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for CompareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CompareError::KeyMismatch { index } => write!(f, "key mismatch at index {index}"),
+            CompareError::OwnerMismatch => f.write_str("account owner mismatch"),
+            CompareError::LengthInvalid => f.write_str("invalid length"),
+            CompareError::NotFound => f.write_str("key not found"),
+            CompareError::DuplicateKey { i, j } => {
+                write!(f, "duplicate key at indices {i} and {j}")
+            }
+            CompareError::RegionFull => f.write_str("key region full"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CompareError {}
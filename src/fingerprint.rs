@@ -0,0 +1,208 @@
+//! Stable 64-bit key fingerprints
+//!
+//! [`fingerprint`] is the one key hash behind the crate's tables and
+//! filters, which all derive their slots and bit positions from it.
+//!
+//! [`FastHash`] and [`FastHashBuilder`] bring the same mixing to std hash
+//! maps, replacing SipHash over 32 bytes with six multiplies per key.
+
+use core::{
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::{Key, fast_eq};
+
+/// Multiplier of each mixing step, `2^64` divided by the golden ratio
+const MIX: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Mixes `word` into `state`: one multiply, then the high half folded down
+#[inline(always)]
+fn mix(state: u64, word: u64) -> u64 {
+    let mixed = (state ^ word).wrapping_mul(MIX);
+    mixed ^ (mixed >> 32)
+}
+
+/// Returns a 64-bit fingerprint of `key`
+///
+/// Public keys are hashes or curve points, so their bytes are already
+/// uniform and a hashing syscall over all 32 of them buys nothing for table
+/// placement. The fingerprint instead mixes in the four words with a
+/// multiply and a shift each, at about 35 instructions on Solana, which also
+/// spreads structured keys such as sysvar ids and vanity addresses.
+///
+/// Equal keys always have equal fingerprints. Distinct keys collide with
+/// probability about `2^-64`, and any subset of the bits is evenly spread,
+/// so both `fingerprint(key) % slots` and `fingerprint(key) >> (64 - bits)`
+/// make good table indices.
+///
+/// The value is stable across crate versions and targets, so it may be
+/// stored in account data. It is [`fingerprint_seeded`] with a seed of zero,
+/// under which the all-zero default key fingerprints to zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fingerprint;
+///
+/// assert_eq!(fingerprint(&[7u8; 32]), fingerprint(&[7u8; 32]));
+/// assert_ne!(fingerprint(&[7u8; 32]), fingerprint(&[8u8; 32]));
+/// ```
+#[inline(always)]
+pub fn fingerprint<K: Key>(key: &K) -> u64 {
+    fingerprint_seeded(key, 0)
+}
+
+/// Returns a 64-bit fingerprint of `key` under `seed`
+///
+/// Different seeds give independent-looking fingerprints of the same key,
+/// for filters that need several positions per key or tables that must not
+/// share a layout with another instance. The computation is fixed:
+///
+/// 1. Read the key as four little-endian words `w0..w3` and start with
+///    `x = seed`
+/// 2. For each word in order, `x = (x ^ w).wrapping_mul(0x9E37_79B9_7F4A_7C15)`
+///    and then `x ^= x >> 32`
+/// 3. Repeat step 2 twice more with a zero word
+/// 4. Return `x`
+///
+/// Chaining the words through the multiply, rather than XOR-folding them,
+/// keeps structured keys (such as arithmetic byte sequences) from cancelling
+/// out, and each shift feeds the well-mixed high bits into the low ones. A
+/// multiply only carries a difference upward, so without the two closing
+/// steps a change in the last byte could not reach the low 24 bits.
+///
+/// This is not a cryptographic hash: anyone can grind keys that collide, so
+/// a fingerprint match must always be confirmed with a full key comparison.
+#[inline(always)]
+pub fn fingerprint_seeded<K: Key>(key: &K, seed: u64) -> u64 {
+    let key = key.key_bytes();
+    let mut state = seed;
+    for at in [0, 8, 16, 24] {
+        let word = u64::from_le_bytes(key[at..at + 8].try_into().unwrap());
+        state = mix(state, word);
+    }
+    mix(mix(state, 0), 0)
+}
+
+/// A key that hashes as its [`fingerprint`]
+///
+/// Use it as the key type of std hash maps and sets, together with
+/// [`FastHashBuilder`], so hashing a key costs one fingerprint instead of
+/// SipHash over 32 bytes:
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use solana_pubkey_compare::{FastHash, FastHashBuilder};
+///
+/// let mut balances: HashMap<FastHash<[u8; 32]>, u64, FastHashBuilder> = HashMap::default();
+/// balances.insert(FastHash([1u8; 32]), 10);
+///
+/// assert_eq!(balances.get(FastHash::from_ref(&[1u8; 32])), Some(&10));
+/// assert_eq!(balances.get(FastHash::from_ref(&[2u8; 32])), None);
+/// ```
+///
+/// The hash is unkeyed, so anyone who controls the keys can grind ones that
+/// share a bucket. That suits indexers over chain data, where keys are
+/// costly to mint in bulk, but not maps over untrusted input at scale.
+///
+/// `FastHash<T>` is `repr(transparent)`, so it is itself a [`Key`].
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct FastHash<T>(pub T);
+
+// SAFETY: `repr(transparent)` over a `Key` has the key's exact layout.
+unsafe impl<T: Key> Key for FastHash<T> {}
+
+impl<T> FastHash<T> {
+    /// Returns the wrapped key
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Views a borrowed key as a `FastHash`, for map lookups
+    #[inline(always)]
+    pub fn from_ref(key: &T) -> &Self {
+        // SAFETY: `Self` is a `repr(transparent)` wrapper around `T`.
+        unsafe { &*(key as *const T as *const Self) }
+    }
+}
+
+impl<T> Deref for FastHash<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for FastHash<T> {
+    #[inline(always)]
+    fn from(key: T) -> Self {
+        FastHash(key)
+    }
+}
+
+impl<T: Key> PartialEq for FastHash<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        fast_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: Key> Eq for FastHash<T> {}
+
+impl<T: Key> Hash for FastHash<T> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(fingerprint(&self.0));
+    }
+}
+
+/// [`BuildHasher`] of [`FastHasher`]s, for maps keyed by [`FastHash`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FastHashBuilder;
+
+impl BuildHasher for FastHashBuilder {
+    type Hasher = FastHasher;
+
+    #[inline(always)]
+    fn build_hasher(&self) -> FastHasher {
+        FastHasher::default()
+    }
+}
+
+/// A [`Hasher`] applying the [`fingerprint`] mixing step to each word written
+///
+/// A single `write_u64` of a [`FastHash`] fingerprint costs one more mixing
+/// step. Other writes are split into little-endian words, the last one
+/// zero-padded, so the hasher also works for composite keys that contain a
+/// `FastHash`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FastHasher {
+    state: u64,
+}
+
+impl Hasher for FastHasher {
+    #[inline(always)]
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0u8; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.state = mix(self.state, u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline(always)]
+    fn write_u64(&mut self, value: u64) {
+        self.state = mix(self.state, value);
+    }
+}
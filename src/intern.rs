@@ -0,0 +1,274 @@
+//! Transaction-scoped interning of keys to small ids
+//!
+//! Programs that compare the same handful of keys over and over in inner
+//! loops can intern each key once, at the boundary where accounts come in,
+//! and compare `u16` ids from then on. Only interning itself touches the full
+//! 32 bytes.
+//!
+//! A [`KeyArena`] holds the interned keys in id order in a fixed layout that
+//! can live in account data, so ids established by one instruction can be
+//! persisted with [`Interner::snapshot`] and picked up by later instructions
+//! with [`Interner::restore`].
+
+use crate::{CompareError, Key, fast_eq, fingerprint};
+
+/// Marks an unused slot in the hash index
+const EMPTY: u16 = u16::MAX;
+
+/// Assigns dense `u16` ids to up to `N` distinct keys in first-seen order
+///
+/// Lookups place keys in an open-addressed index of `N` slots by their
+/// [`fingerprint`] and verify candidates with [`fast_eq`]. Ids are assigned
+/// `0, 1, 2, ...` and never change, so they can index per-key side arrays.
+///
+/// The interner is `34 * N` bytes plus a small header. Solana's stack frames
+/// are 4KB, so interners beyond a few dozen keys belong in a `Box` or, via
+/// the arena, in account data.
+///
+/// ```rust
+/// use solana_pubkey_compare::Interner;
+///
+/// let mut interner = Interner::<16>::new();
+/// let a = interner.intern(&[1u8; 32]).unwrap();
+/// let b = interner.intern(&[2u8; 32]).unwrap();
+/// assert_eq!(interner.intern(&[1u8; 32]), Some(a));
+/// assert_ne!(a, b);
+/// assert_eq!(interner.key_of(b), Some(&[2u8; 32]));
+/// ```
+#[derive(Clone)]
+pub struct Interner<const N: usize> {
+    keys: [[u8; 32]; N],
+    slots: [u16; N],
+    len: u16,
+}
+
+impl<const N: usize> Interner<N> {
+    /// Creates an empty interner
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero or exceeds `u16::MAX`, which is reserved to mark
+    /// empty slots.
+    pub const fn new() -> Self {
+        assert!(
+            N > 0 && N <= u16::MAX as usize,
+            "Interner capacity must be 1..=65535"
+        );
+        Interner {
+            keys: [[0; 32]; N],
+            slots: [EMPTY; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the number of keys interned
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns `true` if no keys have been interned
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of keys this interner can hold
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the id of `key`, assigning the next id if it is new
+    ///
+    /// Returns `None` only when `key` is new and the interner is full.
+    #[inline]
+    pub fn intern<K: Key>(&mut self, key: &K) -> Option<u16> {
+        let key = key.key_bytes();
+        match self.probe(key) {
+            Ok(id) => Some(id),
+            Err(Some(slot)) => {
+                let id = self.len;
+                self.keys[id as usize] = *key;
+                self.slots[slot] = id;
+                self.len += 1;
+                Some(id)
+            }
+            Err(None) => None,
+        }
+    }
+
+    /// Returns the id of `key`, if it has been interned
+    #[inline]
+    pub fn id_of<K: Key>(&self, key: &K) -> Option<u16> {
+        self.probe(key.key_bytes()).ok()
+    }
+
+    /// Returns the key interned under `id`
+    #[inline(always)]
+    pub fn key_of(&self, id: u16) -> Option<&[u8; 32]> {
+        self.keys[..self.len()].get(id as usize)
+    }
+
+    /// Finds `key` in the index, returning its id, or else the empty slot it
+    /// would occupy (`None` if the index is full)
+    #[inline(always)]
+    fn probe(&self, key: &[u8; 32]) -> Result<u16, Option<usize>> {
+        let mut slot = home_slot(key, N);
+        for _ in 0..N {
+            let id = self.slots[slot];
+            if id == EMPTY {
+                return Err(Some(slot));
+            }
+            if fast_eq(&self.keys[id as usize], key) {
+                return Ok(id);
+            }
+            slot = if slot + 1 == N { 0 } else { slot + 1 };
+        }
+        Err(None)
+    }
+}
+
+impl<const N: usize> Interner<N> {
+    /// Writes the interned keys, in id order, into `arena`
+    #[inline]
+    pub fn snapshot(&self, arena: &mut KeyArena<N>) {
+        arena.keys[..self.len()].copy_from_slice(&self.keys[..self.len()]);
+        arena.len = (self.len as u64).to_le_bytes();
+    }
+
+    /// Rebuilds an interner from `arena`, preserving every id
+    ///
+    /// Fails with `LengthInvalid` if the arena's length exceeds `N`, and with
+    /// `DuplicateKey` if a key occurs twice, either of which means the arena
+    /// was not written by [`Interner::snapshot`].
+    pub fn restore(arena: &KeyArena<N>) -> Result<Self, CompareError> {
+        let len = u64::from_le_bytes(arena.len);
+        if len > N as u64 {
+            return Err(CompareError::LengthInvalid);
+        }
+        let mut interner = Self::new();
+        for (j, key) in arena.keys[..len as usize].iter().enumerate() {
+            match interner.probe(key) {
+                Ok(i) => return Err(CompareError::DuplicateKey { i: i as usize, j }),
+                Err(slot) => {
+                    // `len <= N` keys that are all distinct always find a slot
+                    interner.slots[slot.unwrap()] = interner.len;
+                    interner.keys[j] = *key;
+                    interner.len += 1;
+                }
+            }
+        }
+        Ok(interner)
+    }
+}
+
+impl<const N: usize> Default for Interner<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Index slot a key's probe starts at
+#[inline(always)]
+fn home_slot(key: &[u8; 32], slots: usize) -> usize {
+    (fingerprint(key) % slots as u64) as usize
+}
+
+/// Interned keys in id order, laid out for storage in account data
+///
+/// | Offset | Size | Field                    |
+/// |--------|------|--------------------------|
+/// | 0      | 8    | Number of keys (u64, LE) |
+/// | 8      | 32*N | Keys in id order         |
+///
+/// The layout has alignment 1, so [`KeyArena::from_bytes`] can view account
+/// data at any offset in place.
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct KeyArena<const N: usize> {
+    len: [u8; 8],
+    keys: [[u8; 32]; N],
+}
+
+impl<const N: usize> KeyArena<N> {
+    /// Size of the encoded arena in bytes
+    pub const SIZE: usize = 8 + 32 * N;
+
+    /// Creates an empty arena
+    pub const fn new() -> Self {
+        KeyArena {
+            len: [0; 8],
+            keys: [[0; 32]; N],
+        }
+    }
+
+    /// Views the first [`KeyArena::SIZE`] bytes of `data` as an arena
+    #[inline]
+    pub fn from_bytes(data: &[u8]) -> Option<&Self> {
+        let data = data.get(..Self::SIZE)?;
+        // SAFETY: `KeyArena` is `repr(C)` over byte arrays only, so it has
+        // alignment 1, no padding, and every bit pattern is valid.
+        Some(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    /// Views the first [`KeyArena::SIZE`] bytes of `data` as a mutable arena
+    #[inline]
+    pub fn from_bytes_mut(data: &mut [u8]) -> Option<&mut Self> {
+        let data = data.get_mut(..Self::SIZE)?;
+        // SAFETY: as in `from_bytes`.
+        Some(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    /// Returns the number of keys stored, clamped to `N` should the length
+    /// field be corrupt
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        u64::from_le_bytes(self.len).min(N as u64) as usize
+    }
+
+    /// Returns `true` if no keys are stored
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of keys the arena can hold
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the key with id `id`
+    #[inline(always)]
+    pub fn get(&self, id: u16) -> Option<&[u8; 32]> {
+        self.keys().get(id as usize)
+    }
+
+    /// Returns the stored keys in id order
+    #[inline(always)]
+    pub fn keys(&self) -> &[[u8; 32]] {
+        &self.keys[..self.len()]
+    }
+
+    /// Iterates over `(id, key)` pairs in id order
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &[u8; 32])> {
+        self.keys()
+            .iter()
+            .enumerate()
+            .map(|(id, key)| (id as u16, key))
+    }
+
+    /// Removes every key
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = [0; 8];
+    }
+}
+
+impl<const N: usize> Default for KeyArena<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,87 @@
+//! The [`Key`] trait: types whose memory is a 32-byte public key
+
+/// Types laid out in memory as a 32-byte public key
+///
+/// Every comparison routine in this crate is generic over `Key`. The assembly
+/// reads keys directly through the reference it is given, so the trait is
+/// implemented only for types whose representation *is* the key bytes:
+///
+/// - `[u8; 32]`
+/// - `[u64; 4]` and `[u128; 2]`, for zero-copy structs that store keys as
+///   words to get 8- or 16-byte alignment
+/// - [`AlignedPubkey`](crate::AlignedPubkey), an 8-byte-aligned key
+/// - `solana_pubkey::Pubkey`, which `solana_program::pubkey::Pubkey`
+///   re-exports in 2.x (with the default `solana-pubkey` feature)
+///
+/// Other 32-byte key types, such as `Pubkey` from another major version,
+/// compare through their bytes: `fast_eq(a.as_array(), b.as_array())`, or
+/// [`fast_eq_unaligned`](crate::fast_eq_unaligned) over `as_ref()` slices.
+///
+/// Word-array keys are compared by their in-memory (little-endian) bytes, so
+/// a `[u64; 4]` loaded from account data compares equal to the `[u8; 32]`
+/// stored at the same location.
+///
+/// # Safety
+///
+/// Implementors must be exactly 32 bytes with no padding, every byte must be
+/// part of the key, and every bit pattern must be a valid value. Slices of
+/// `Self` are therefore tightly packed arrays of keys with a 32-byte stride,
+/// which the batch routines rely on.
+pub unsafe trait Key: Sized {
+    /// Returns the 32 key bytes backing this value
+    #[inline(always)]
+    fn key_bytes(&self) -> &[u8; 32] {
+        // SAFETY: the trait contract guarantees `Self` is exactly 32 key bytes,
+        // and `[u8; 32]` has no alignment requirement.
+        unsafe { &*(self as *const Self as *const [u8; 32]) }
+    }
+
+    /// Returns a pointer to the first key byte, as passed to the assembly
+    #[inline(always)]
+    fn key_ptr(&self) -> *const u8 {
+        self as *const Self as *const u8
+    }
+}
+
+unsafe impl Key for [u8; 32] {}
+unsafe impl Key for [u64; 4] {}
+unsafe impl Key for [u128; 2] {}
+
+#[cfg(feature = "solana-pubkey")]
+unsafe impl Key for solana_pubkey::Pubkey {}
+
+/// Things that have a key: bare keys and the account types that carry one
+///
+/// Lets helpers such as [`same_key`](crate::same_key) accept an account or a
+/// key interchangeably, so call sites need no `.key` / `.key()`:
+///
+/// - every [`Key`] type, yielding itself
+/// - `AccountInfo` (with the `solana-program` feature)
+/// - Anchor's `Account`, `AccountLoader`, `InterfaceAccount`, `Signer`,
+///   `SystemAccount` and `UncheckedAccount` (with the `anchor` feature)
+pub trait KeyOf {
+    /// Key type this value carries
+    type Key: Key;
+
+    /// Returns a reference to the key, without copying it
+    fn key_of(&self) -> &Self::Key;
+}
+
+impl<K: Key> KeyOf for K {
+    type Key = K;
+
+    #[inline(always)]
+    fn key_of(&self) -> &K {
+        self
+    }
+}
+
+#[cfg(feature = "solana-program")]
+impl KeyOf for solana_program::account_info::AccountInfo<'_> {
+    type Key = solana_program::pubkey::Pubkey;
+
+    #[inline(always)]
+    fn key_of(&self) -> &Self::Key {
+        self.key
+    }
+}
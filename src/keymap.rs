@@ -0,0 +1,265 @@
+//! Fixed-capacity key-to-value maps that live in account data
+//!
+//! A [`PubkeyMap`] is an open-addressing hash table whose slots are placed by
+//! [`fingerprint`]. Each slot carries a one-byte tag from the fingerprint, so
+//! a probe only runs the full key comparison when the tags agree, and removal
+//! shifts later entries back instead of leaving tombstones, so the table never
+//! degrades with churn.
+
+use core::mem::{align_of, size_of};
+
+use crate::{CompareError, Key, fast_eq, fingerprint};
+
+/// A map from keys to `V` with room for `N` entries and no heap allocation
+///
+/// Lookups start at the slot `fingerprint(key) % N` and walk forward until
+/// the key or an empty slot is found. Occupied slots whose tag differs from
+/// the key's are skipped for one byte comparison; on a tag hit the keys are
+/// compared with [`fast_eq`]. Keep the map at most about three quarters full
+/// for short probes.
+///
+/// The layout is `repr(C)`, suitable for zero-copy account data:
+///
+/// | Offset     | Size             | Field                              |
+/// |------------|------------------|------------------------------------|
+/// | 0          | 8                | Number of entries (u64, LE)        |
+/// | 8          | N                | Slot tags (0 for an empty slot)    |
+/// | 8 + N      | 32*N             | Slot keys (zero for an empty slot) |
+/// | aligned up | N * size_of::<V> | Slot values                        |
+///
+/// Values start at the next multiple of `V`'s alignment, and
+/// [`PubkeyMap::SIZE`] includes any padding.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::PubkeyMap;
+///
+/// let mut balances = PubkeyMap::<8, u64>::new();
+/// assert_eq!(balances.insert(&[1u8; 32], 10), Ok(None));
+/// assert_eq!(balances.insert(&[1u8; 32], 15), Ok(Some(10)));
+/// assert_eq!(balances.get(&[1u8; 32]), Some(&15));
+///
+/// assert_eq!(balances.remove(&[1u8; 32]), Some(15));
+/// assert!(balances.is_empty());
+/// ```
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct PubkeyMap<const N: usize, V: Copy> {
+    len: [u8; 8],
+    tags: [u8; N],
+    keys: [[u8; 32]; N],
+    values: [V; N],
+}
+
+impl<const N: usize, V: Copy> PubkeyMap<N, V> {
+    /// Size of the encoded map in bytes
+    pub const SIZE: usize = size_of::<Self>();
+
+    /// Creates an empty map, with every value slot set to `V::default()`
+    pub fn new() -> Self
+    where
+        V: Default,
+    {
+        PubkeyMap {
+            len: [0; 8],
+            tags: [0; N],
+            keys: [[0; 32]; N],
+            values: [V::default(); N],
+        }
+    }
+
+    /// Views the first [`PubkeyMap::SIZE`] bytes of `data` as a map
+    ///
+    /// Returns `None` if `data` is too short or not aligned for `V`.
+    ///
+    /// # Safety
+    ///
+    /// Every bit pattern the value slots may hold must be a valid `V`, as for
+    /// integers and arrays of them. The key and tag fields accept any bytes.
+    #[inline]
+    pub unsafe fn from_bytes(data: &[u8]) -> Option<&Self> {
+        let data = data.get(..Self::SIZE)?;
+        if data.as_ptr().align_offset(align_of::<Self>()) != 0 {
+            return None;
+        }
+        // SAFETY: the bytes are in bounds and aligned for `Self`, whose other
+        // fields are byte arrays; the caller vouches for the values.
+        Some(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    /// Views the first [`PubkeyMap::SIZE`] bytes of `data` as a mutable map
+    ///
+    /// Returns `None` if `data` is too short or not aligned for `V`.
+    ///
+    /// # Safety
+    ///
+    /// As for [`PubkeyMap::from_bytes`].
+    #[inline]
+    pub unsafe fn from_bytes_mut(data: &mut [u8]) -> Option<&mut Self> {
+        let data = data.get_mut(..Self::SIZE)?;
+        if data.as_ptr().align_offset(align_of::<Self>()) != 0 {
+            return None;
+        }
+        // SAFETY: as in `from_bytes`.
+        Some(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    /// Returns the number of entries stored, clamped to `N` should the
+    /// length field be corrupt
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        u64::from_le_bytes(self.len).min(N as u64) as usize
+    }
+
+    /// Returns `true` if no entries are stored
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of entries the map can hold
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if `key` has an entry
+    #[inline]
+    pub fn contains_key<K: Key>(&self, key: &K) -> bool {
+        self.find(key.key_bytes()).is_ok()
+    }
+
+    /// Returns the value stored for `key`
+    #[inline]
+    pub fn get<K: Key>(&self, key: &K) -> Option<&V> {
+        let slot = self.find(key.key_bytes()).ok()?;
+        Some(&self.values[slot])
+    }
+
+    /// Returns the value stored for `key`, mutably
+    #[inline]
+    pub fn get_mut<K: Key>(&mut self, key: &K) -> Option<&mut V> {
+        let slot = self.find(key.key_bytes()).ok()?;
+        Some(&mut self.values[slot])
+    }
+
+    /// Stores `value` for `key`, returning the value it replaced
+    ///
+    /// Returns [`CompareError::RegionFull`] if `key` has no entry and the
+    /// map holds `N` entries.
+    #[inline]
+    pub fn insert<K: Key>(&mut self, key: &K, value: V) -> Result<Option<V>, CompareError> {
+        let key = key.key_bytes();
+        match self.find(key) {
+            Ok(slot) => Ok(Some(core::mem::replace(&mut self.values[slot], value))),
+            Err(Some(slot)) if self.len() < N => {
+                self.tags[slot] = tag(fingerprint(key));
+                self.keys[slot] = *key;
+                self.values[slot] = value;
+                self.set_len(self.len() + 1);
+                Ok(None)
+            }
+            Err(_) => Err(CompareError::RegionFull),
+        }
+    }
+
+    /// Removes the entry for `key`, returning its value
+    ///
+    /// Entries probed past the freed slot are shifted back into it, so no
+    /// tombstone is left behind.
+    #[inline]
+    pub fn remove<K: Key>(&mut self, key: &K) -> Option<V> {
+        let mut hole = self.find(key.key_bytes()).ok()?;
+        let value = self.values[hole];
+
+        let mut slot = hole;
+        for _ in 1..N {
+            slot = if slot + 1 == N { 0 } else { slot + 1 };
+            if self.tags[slot] == 0 {
+                break;
+            }
+            // An entry may fill the hole unless its home slot lies cyclically
+            // in `(hole, slot]`, where the hole would be before its probe start
+            let home = home(fingerprint(&self.keys[slot]), N);
+            let stays = if hole <= slot {
+                hole < home && home <= slot
+            } else {
+                hole < home || home <= slot
+            };
+            if !stays {
+                self.tags[hole] = self.tags[slot];
+                self.keys[hole] = self.keys[slot];
+                self.values[hole] = self.values[slot];
+                hole = slot;
+            }
+        }
+
+        self.tags[hole] = 0;
+        self.keys[hole] = [0; 32];
+        self.set_len(self.len().saturating_sub(1));
+        Some(value)
+    }
+
+    /// Iterates over the stored `(key, value)` pairs in slot order
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&[u8; 32], &V)> {
+        self.tags
+            .iter()
+            .zip(self.keys.iter().zip(&self.values))
+            .filter(|(tag, _)| **tag != 0)
+            .map(|(_, entry)| entry)
+    }
+
+    /// Removes every entry, zeroing the tags and keys
+    #[inline]
+    pub fn clear(&mut self) {
+        self.tags = [0; N];
+        self.keys = [[0; 32]; N];
+        self.set_len(0);
+    }
+
+    /// Returns the slot holding `key`, or else the empty slot where the
+    /// probe ended (`None` if every slot was visited)
+    #[inline(always)]
+    fn find(&self, key: &[u8; 32]) -> Result<usize, Option<usize>> {
+        if N == 0 {
+            return Err(None);
+        }
+        let fingerprint = fingerprint(key);
+        let tag = tag(fingerprint);
+        let mut slot = home(fingerprint, N);
+        for _ in 0..N {
+            match self.tags[slot] {
+                0 => return Err(Some(slot)),
+                stored if stored == tag && fast_eq(&self.keys[slot], key) => return Ok(slot),
+                _ => {}
+            }
+            slot = if slot + 1 == N { 0 } else { slot + 1 };
+        }
+        Err(None)
+    }
+
+    #[inline(always)]
+    fn set_len(&mut self, len: usize) {
+        self.len = (len as u64).to_le_bytes();
+    }
+}
+
+impl<const N: usize, V: Copy + Default> Default for PubkeyMap<N, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Non-zero slot tag from a fingerprint's top 7 bits
+#[inline(always)]
+fn tag(fingerprint: u64) -> u8 {
+    (fingerprint >> 57) as u8 | 0x80
+}
+
+/// Slot a fingerprint's probe starts at
+#[inline(always)]
+fn home(fingerprint: u64, slots: usize) -> usize {
+    (fingerprint % slots as u64) as usize
+}
@@ -0,0 +1,188 @@
+//! Fixed-capacity key sets that live on the stack or in account data
+//!
+//! A [`PubkeySet`] keeps up to `N` distinct keys in a fixed layout with
+//! alignment 1, so an allowlist can be viewed in place in account data and
+//! edited without slice bookkeeping at the call site.
+
+use crate::{CompareError, Key, binary_search_keys, contains, position_of};
+
+/// A set of at most `N` distinct keys with no heap allocation
+///
+/// With `SORTED = false` (the default) keys are kept in insertion order:
+/// lookups are the [`contains`] assembly loop, at 4 instructions per key
+/// that differs in its first 8 bytes, and removal moves the last key into
+/// the gap. With `SORTED = true` keys are kept in byte order: lookups are a
+/// [`binary_search_keys`] over the `fast_cmp` comparator, and insertion and
+/// removal shift the keys behind the slot. Pick the sorted form once the set
+/// grows past a few dozen keys or when it must be iterated in order.
+///
+/// | Offset | Size | Field                    |
+/// |--------|------|--------------------------|
+/// | 0      | 8    | Number of keys (u64, LE) |
+/// | 8      | 32*N | Keys, then zeroed slots  |
+///
+/// The layout has alignment 1, so [`PubkeySet::from_bytes_mut`] can view
+/// account data at any offset in place. A sorted set read from data that
+/// was not written in order answers lookups incorrectly but safely.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::PubkeySet;
+///
+/// let mut allowlist = PubkeySet::<4, true>::new();
+/// assert_eq!(allowlist.insert(&[3u8; 32]), Ok(true));
+/// assert_eq!(allowlist.insert(&[1u8; 32]), Ok(true));
+/// assert_eq!(allowlist.insert(&[3u8; 32]), Ok(false));
+///
+/// assert!(allowlist.contains(&[1u8; 32]));
+/// assert_eq!(allowlist.keys(), &[[1u8; 32], [3u8; 32]]);
+/// assert!(allowlist.remove(&[1u8; 32]));
+/// assert!(!allowlist.contains(&[1u8; 32]));
+/// ```
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct PubkeySet<const N: usize, const SORTED: bool = false> {
+    len: [u8; 8],
+    keys: [[u8; 32]; N],
+}
+
+impl<const N: usize, const SORTED: bool> PubkeySet<N, SORTED> {
+    /// Size of the encoded set in bytes
+    pub const SIZE: usize = 8 + 32 * N;
+
+    /// Creates an empty set
+    pub const fn new() -> Self {
+        PubkeySet {
+            len: [0; 8],
+            keys: [[0; 32]; N],
+        }
+    }
+
+    /// Views the first [`PubkeySet::SIZE`] bytes of `data` as a set
+    #[inline]
+    pub fn from_bytes(data: &[u8]) -> Option<&Self> {
+        let data = data.get(..Self::SIZE)?;
+        // SAFETY: `PubkeySet` is `repr(C)` over byte arrays only, so it has
+        // alignment 1, no padding, and every bit pattern is valid.
+        Some(unsafe { &*(data.as_ptr() as *const Self) })
+    }
+
+    /// Views the first [`PubkeySet::SIZE`] bytes of `data` as a mutable set
+    #[inline]
+    pub fn from_bytes_mut(data: &mut [u8]) -> Option<&mut Self> {
+        let data = data.get_mut(..Self::SIZE)?;
+        // SAFETY: as in `from_bytes`.
+        Some(unsafe { &mut *(data.as_mut_ptr() as *mut Self) })
+    }
+
+    /// Returns the number of keys stored, clamped to `N` should the length
+    /// field be corrupt
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        u64::from_le_bytes(self.len).min(N as u64) as usize
+    }
+
+    /// Returns `true` if no keys are stored
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of keys the set can hold
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the stored keys, in byte order if `SORTED`
+    #[inline(always)]
+    pub fn keys(&self) -> &[[u8; 32]] {
+        &self.keys[..self.len()]
+    }
+
+    /// Returns `true` if `key` is in the set
+    #[inline]
+    pub fn contains<K: Key>(&self, key: &K) -> bool {
+        let key = key.key_bytes();
+        if SORTED {
+            binary_search_keys(self.keys(), key).is_ok()
+        } else {
+            contains(self.keys(), key)
+        }
+    }
+
+    /// Adds `key` to the set
+    ///
+    /// Returns `Ok(false)` if the key was already present, and
+    /// [`CompareError::RegionFull`] if it is absent and the set holds `N`
+    /// keys.
+    #[inline]
+    pub fn insert<K: Key>(&mut self, key: &K) -> Result<bool, CompareError> {
+        let key = key.key_bytes();
+        let len = self.len();
+        let slot = if SORTED {
+            match binary_search_keys(self.keys(), key) {
+                Ok(_) => return Ok(false),
+                Err(slot) => slot,
+            }
+        } else if contains(self.keys(), key) {
+            return Ok(false);
+        } else {
+            len
+        };
+        if len == N {
+            return Err(CompareError::RegionFull);
+        }
+
+        self.keys.copy_within(slot..len, slot + 1);
+        self.keys[slot] = *key;
+        self.set_len(len + 1);
+        Ok(true)
+    }
+
+    /// Removes `key` from the set, returning `true` if it was present
+    ///
+    /// The vacated slot at the end of the keys is zeroed.
+    #[inline]
+    pub fn remove<K: Key>(&mut self, key: &K) -> bool {
+        let key = key.key_bytes();
+        let len = self.len();
+        let slot = if SORTED {
+            binary_search_keys(self.keys(), key).ok()
+        } else {
+            position_of(self.keys(), key)
+        };
+        let Some(slot) = slot else {
+            return false;
+        };
+
+        if SORTED {
+            self.keys.copy_within(slot + 1..len, slot);
+        } else {
+            self.keys[slot] = self.keys[len - 1];
+        }
+        self.keys[len - 1] = [0; 32];
+        self.set_len(len - 1);
+        true
+    }
+
+    /// Removes every key, zeroing their slots
+    #[inline]
+    pub fn clear(&mut self) {
+        let len = self.len();
+        self.keys[..len].fill([0; 32]);
+        self.set_len(0);
+    }
+
+    #[inline(always)]
+    fn set_len(&mut self, len: usize) {
+        self.len = (len as u64).to_le_bytes();
+    }
+}
+
+impl<const N: usize, const SORTED: bool> Default for PubkeySet<N, SORTED> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,107 @@
+//! Compile-time key comparisons and the static assertions built on them
+//!
+//! `const fn` cannot call the assembly routines, so these compare byte by
+//! byte. They are meant for `const` items, where the cost is paid by the
+//! compiler rather than in compute units.
+
+/// Compares two keys for equality in a `const` context
+pub const fn const_key_eq(lhs: &[u8; 32], rhs: &[u8; 32]) -> bool {
+    let mut i = 0;
+    while i < 32 {
+        if lhs[i] != rhs[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Orders two keys byte-lexicographically in a `const` context
+///
+/// Matches the order used by the sorted set routines, so a table that passes
+/// [`static_assert_sorted!`](crate::static_assert_sorted) is valid input for
+/// them.
+pub const fn const_key_cmp(lhs: &[u8; 32], rhs: &[u8; 32]) -> core::cmp::Ordering {
+    let mut i = 0;
+    while i < 32 {
+        if lhs[i] < rhs[i] {
+            return core::cmp::Ordering::Less;
+        }
+        if lhs[i] > rhs[i] {
+            return core::cmp::Ordering::Greater;
+        }
+        i += 1;
+    }
+    core::cmp::Ordering::Equal
+}
+
+/// Returns `true` if `keys` is in non-decreasing [`const_key_cmp`] order, in a
+/// `const` context
+pub const fn const_keys_sorted(keys: &[[u8; 32]]) -> bool {
+    let mut i = 1;
+    while i < keys.len() {
+        if const_key_cmp(&keys[i - 1], &keys[i]).is_gt() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Returns `true` if no two keys in `keys` are equal, in a `const` context
+pub const fn const_keys_distinct(keys: &[[u8; 32]]) -> bool {
+    let mut i = 0;
+    while i < keys.len() {
+        let mut j = i + 1;
+        while j < keys.len() {
+            if const_key_eq(&keys[i], &keys[j]) {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Fails compilation if any two of the given `[u8; 32]` constants are equal
+///
+/// Catches copy-paste errors in hardcoded program and authority keys before
+/// they ship:
+///
+/// ```rust,ignore
+/// const ADMIN: [u8; 32] = [1; 32];
+/// const TREASURY: [u8; 32] = [2; 32];
+/// const FEE_VAULT: [u8; 32] = [1; 32];
+///
+/// // error[E0080]: evaluation panicked: static_assert_distinct: two keys are equal
+/// static_assert_distinct!(ADMIN, TREASURY, FEE_VAULT);
+/// ```
+#[macro_export]
+macro_rules! static_assert_distinct {
+    ($($key:expr),+ $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::const_keys_distinct(&[$($key),+]),
+            "static_assert_distinct: two keys are equal",
+        );
+    };
+}
+
+/// Fails compilation unless a static key table is sorted
+///
+/// Tables meant for binary search must be in byte-lexicographic order; an
+/// unsorted table makes lookups silently miss keys that are present.
+///
+/// ```rust,ignore
+/// const ALLOWLIST: [[u8; 32]; 3] = [[1; 32], [2; 32], [3; 32]];
+/// static_assert_sorted!(ALLOWLIST);
+/// ```
+#[macro_export]
+macro_rules! static_assert_sorted {
+    ($table:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::const_keys_sorted(&$table),
+            "static_assert_sorted: table is not sorted",
+        );
+    };
+}
@@ -1,60 +1,281 @@
 #![allow(unexpected_cfgs)]
 #![doc = include_str!("../README.md")]
+//! High-performance Solana public key comparison library
+//!
+//! This crate provides ultra-fast public key comparison for Solana blockchain programs,
+//! achieving significant performance improvements through hand-optimized BPF assembly.
+//!
+//! ## Performance
+//!
+//! - **Assembly implementation**: 19 compute units on Solana BPF
+//! - **Standard comparison**: 28 compute units on Solana BPF
+//! - **Improvement**: ~32% reduction in compute units
+//!
+//! ## Features
+//!
+//! - Zero dependencies and `#[no_std]` compatible
+//! - Hand-optimized BPF assembly for Solana runtime
+//! - Automatic fallback to standard comparison for native testing
+//! - Generic interface supporting any 32-byte key types
+//! - Compile-time safety with Rust's type system
+//!
+//! ## Usage
+//!
+//! ```rust
+//! use solana_pubkey_compare::fast_eq;
+//! use solana_program::pubkey::Pubkey;
+//!
+//! // Compare Solana Pubkeys
+//! let key1 = Pubkey::new_unique();
+//! let key2 = Pubkey::new_unique();
+//!
+//! if fast_eq(&key1, &key2) {
+//!     // Keys are equal
+//! }
+//!
+//! // Works with any 32-byte types
+//! let bytes1: [u8; 32] = [0; 32];
+//! let bytes2: [u8; 32] = [1; 32];
+//! assert!(!fast_eq(&bytes1, &bytes2));
+//! ```
+//!
+//! ## Implementation Details
+//!
+//! The assembly implementation performs parallel 64-bit comparisons:
+//! 1. Loads four 8-byte chunks from each key simultaneously
+//! 2. Uses BPF conditional jumps for early exit on first mismatch
+//! 3. Minimizes instruction count and memory access overhead
+//!
+//! On native platforms, falls back to a standard byte-array comparison
+//! for compatibility with testing and development workflows.
+
+#[cfg(feature = "solana-program")]
+pub mod account;
+mod aligned;
+#[cfg(feature = "scan")]
+pub mod alt;
+#[cfg(feature = "anchor")]
+pub mod anchor;
+#[cfg(feature = "hash")]
+mod bloom;
+pub mod costs;
+#[cfg(feature = "solana-program")]
+mod diag;
+#[cfg(all(feature = "std", feature = "hash"))]
+mod digest;
+mod error;
+#[cfg(feature = "hash")]
+mod fingerprint;
+#[cfg(feature = "hash")]
+mod intern;
+mod key;
+#[cfg(feature = "hash")]
+mod keymap;
+#[cfg(feature = "scan")]
+mod keyset;
+mod konst;
+#[cfg(all(feature = "std", feature = "solana-program", feature = "scan"))]
+mod meta;
+#[cfg(feature = "metaplex")]
+pub mod metaplex;
+#[cfg(feature = "scan")]
+pub mod mint;
+#[cfg(feature = "scan")]
+mod multi;
+mod nonzero;
+#[cfg(feature = "scan")]
+mod ord;
+pub mod parse;
+#[cfg(feature = "solana-program")]
+mod require;
+pub mod routine;
+#[cfg(feature = "scan")]
+mod scan;
+#[cfg(feature = "cmp")]
+mod seeds;
+#[cfg(feature = "scan")]
+mod select;
+#[cfg(feature = "scan")]
+mod set;
+#[cfg(feature = "shadow")]
+pub mod shadow;
+#[cfg(feature = "copy")]
+mod slot;
+#[cfg(feature = "scan")]
+mod sorted;
+#[cfg(feature = "scan")]
+pub mod stake_pool;
+#[cfg(feature = "scan")]
+mod table;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+#[cfg(feature = "scan")]
+pub mod token;
+
+pub use aligned::{AlignedPubkey, fast_eq_aligned};
+#[cfg(feature = "hash")]
+pub use bloom::PubkeyBloom;
+#[cfg(all(feature = "std", feature = "hash"))]
+pub use digest::KeySetDigest;
+pub use error::{CompareError, ERROR_CODE_BASE};
+#[cfg(feature = "hash")]
+pub use fingerprint::{FastHash, FastHashBuilder, FastHasher, fingerprint, fingerprint_seeded};
+#[cfg(feature = "hash")]
+pub use intern::{Interner, KeyArena};
+pub use key::{Key, KeyOf};
+#[cfg(feature = "hash")]
+pub use keymap::PubkeyMap;
+#[cfg(feature = "scan")]
+pub use keyset::PubkeySet;
+pub use konst::{const_key_cmp, const_key_eq, const_keys_distinct, const_keys_sorted};
+#[cfg(all(feature = "std", feature = "solana-program", feature = "scan"))]
+pub use meta::sanitize_metas;
+#[cfg(feature = "scan")]
+pub use multi::MultiBufferSearch;
+pub use nonzero::NonZeroPubkey;
+#[cfg(feature = "scan")]
+pub use ord::FastOrd;
+#[cfg(feature = "solana-program")]
+pub use require::{
+    FailureMode, Guard, assert_keys_eq, require_fee_payer, require_keys_eq, require_keys_neq,
+    require_same_key, require_signer_at,
+};
+#[cfg(feature = "scan")]
+pub use scan::{
+    all_equal, any_eq, contains, count_eq, count_members, fast_eq_at, find_key_in_bytes,
+    find_zero_slot, position_of, scan_keys, slices_eq, validate_keys,
+};
+#[cfg(feature = "cmp")]
+pub use seeds::seeds_eq;
+#[cfg(feature = "scan")]
+pub use select::{max_key, min_key, partition_eq, select_k_smallest, select_kth};
+#[cfg(feature = "scan")]
+pub use set::{
+    all_unique, all_unique_in_place, dedup_in_place, difference_sorted, intersect_sorted,
+    is_subset_of, is_subset_of_unsorted, is_superset_of, locks_conflict, set_eq, set_eq_in_place,
+    symmetric_difference, union_sorted,
+};
+#[cfg(all(feature = "copy", feature = "solana-program"))]
+pub use slot::init_or_require;
+#[cfg(feature = "copy")]
+pub use slot::key_cas;
+#[cfg(feature = "macros")]
+pub use solana_pubkey_compare_macros::{include_keys, key_lookup};
+#[cfg(feature = "scan")]
+pub use sorted::{binary_search_keys, insert_sorted, is_sorted, remove_sorted, sort_keys};
+#[cfg(feature = "scan")]
+pub use table::KeyTable;
 
 unsafe extern "C" {
     fn __solana_pubkey_compare__fast_eq(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_branchless(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_u64(lhs_ptr: *const u8, rhs_ptr: *const u8) -> u64;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_assert_eq(lhs_ptr: *const u8, rhs_ptr: *const u8);
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_assert_eq_with_code(
+        lhs_ptr: *const u8,
+        rhs_ptr: *const u8,
+        code: u64,
+    );
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_ptrcheck(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_rev(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_ne(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_cmp(lhs_ptr: *const u8, rhs_ptr: *const u8) -> i8;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_is_zero(key_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_64(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_8(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_16(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__fast_eq_128(lhs_ptr: *const u8, rhs_ptr: *const u8) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__eq_pairs(
+        a1_ptr: *const u8,
+        b1_ptr: *const u8,
+        a2_ptr: *const u8,
+        b2_ptr: *const u8,
+    ) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__prefix_eq(lhs_ptr: *const u8, rhs_ptr: *const u8, n: u64) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__masked_eq(
+        lhs_ptr: *const u8,
+        rhs_ptr: *const u8,
+        mask_ptr: *const u8,
+    ) -> bool;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__diff_index(lhs_ptr: *const u8, rhs_ptr: *const u8) -> u64;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__first_diff_qword(lhs_ptr: *const u8, rhs_ptr: *const u8) -> u8;
+    #[cfg(feature = "cmp")]
+    fn __solana_pubkey_compare__eq_either(
+        key_ptr: *const u8,
+        a_ptr: *const u8,
+        b_ptr: *const u8,
+    ) -> bool;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__contains(
+        haystack_ptr: *const u8,
+        len: u64,
+        needle_ptr: *const u8,
+    ) -> bool;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__position_of(
+        haystack_ptr: *const u8,
+        len: u64,
+        needle_ptr: *const u8,
+    ) -> u64;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__count_eq(
+        haystack_ptr: *const u8,
+        len: u64,
+        needle_ptr: *const u8,
+    ) -> u64;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__scan_strided(
+        base_ptr: *const u8,
+        count: u64,
+        stride: u64,
+        needle_ptr: *const u8,
+    ) -> u64;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__slices_eq(lhs_ptr: *const u8, len: u64, rhs_ptr: *const u8)
+    -> bool;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__all_equal(keys_ptr: *const u8, len: u64) -> bool;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__validate_keys(
+        expected_ptr: *const u8,
+        len: u64,
+        actual_ptr: *const *const u8,
+    ) -> u64;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__count_members(
+        signers_ptr: *const *const u8,
+        signers_len: u64,
+        members_ptr: *const u8,
+        members_len: u64,
+    ) -> u64;
+    #[cfg(feature = "scan")]
+    fn __solana_pubkey_compare__find_zero_slot(data_ptr: *const u8, count: u64) -> u64;
+    #[cfg(feature = "copy")]
+    fn __solana_pubkey_compare__key_cas(
+        slot_ptr: *mut u8,
+        expected_ptr: *const u8,
+        new_ptr: *const u8,
+    ) -> bool;
 }
 
-/// High-performance Solana public key comparison library
-///
-/// This crate provides ultra-fast public key comparison for Solana blockchain programs,
-/// achieving significant performance improvements through hand-optimized BPF assembly.
-///
-/// ## Performance
-///
-/// - **Assembly implementation**: 19 compute units on Solana BPF
-/// - **Standard comparison**: 28 compute units on Solana BPF
-/// - **Improvement**: ~32% reduction in compute units
-///
-/// ## Features
-///
-/// - Zero dependencies and `#[no_std]` compatible
-/// - Hand-optimized BPF assembly for Solana runtime
-/// - Automatic fallback to standard comparison for native testing
-/// - Generic interface supporting any 32-byte key types
-/// - Compile-time safety with Rust's type system
-///
-/// ## Usage
-///
-/// ```rust
-/// use solana_pubkey_compare::fast_eq;
-/// use solana_program::pubkey::Pubkey;
-///
-/// // Compare Solana Pubkeys
-/// let key1 = Pubkey::new_unique();
-/// let key2 = Pubkey::new_unique();
-///
-/// if fast_eq(&key1, &key2) {
-///     // Keys are equal
-/// }
-///
-/// // Works with any 32-byte types
-/// let bytes1: [u8; 32] = [0; 32];
-/// let bytes2: [u8; 32] = [1; 32];
-/// assert!(!fast_eq(&bytes1, &bytes2));
-/// ```
-///
-/// ## Implementation Details
-///
-/// The assembly implementation performs parallel 64-bit comparisons:
-/// 1. Loads four 8-byte chunks from each key simultaneously
-/// 2. Uses BPF conditional jumps for early exit on first mismatch
-/// 3. Minimizes instruction count and memory access overhead
-///
-/// On native platforms, falls back to the standard `PartialEq` implementation
-/// for compatibility with testing and development workflows.
-
-
 /// Ultra-fast public key equality comparison using optimized BPF assembly
 ///
 /// This function provides maximum performance for comparing 32-byte public keys
@@ -64,7 +285,7 @@ unsafe extern "C" {
 /// # Performance
 ///
 /// - **On Solana BPF**: 19 compute units (32% faster than standard comparison)
-/// - **On native**: Falls back to `PartialEq` for testing compatibility
+/// - **On native**: Falls back to a byte-array comparison for testing compatibility
 ///
 /// # Examples
 ///
@@ -84,13 +305,18 @@ unsafe extern "C" {
 /// let array1 = [1u8; 32];
 /// let array2 = [1u8; 32];
 /// assert!(fast_eq(&array1, &array2));
+///
+/// // Including word arrays stored for alignment in zero-copy structs
+/// let words1 = [0x0101_0101_0101_0101u64; 4];
+/// let words2 = [0x0101_0101_0101_0101u64; 4];
+/// assert!(fast_eq(&words1, &words2));
 /// ```
 ///
 /// # Type Requirements
 ///
-/// The generic type `T` must implement:
-/// - `AsRef<[u8]>` - For accessing the underlying byte data
-/// - `PartialEq` - For the native fallback implementation
+/// The generic type `T` must implement [`Key`], which covers `[u8; 32]`,
+/// `[u64; 4]`, `[u128; 2]` and (with the default `solana-pubkey` feature)
+/// `Pubkey`.
 ///
 /// # Safety
 ///
@@ -99,7 +325,8 @@ unsafe extern "C" {
 /// invariants are maintained:
 ///
 /// - References are valid for the duration of the call
-/// - Data alignment is handled by the BPF runtime
+/// - Keys need no alignment: sBPF loads accept any address (see
+///   [`fast_eq_unaligned`])
 /// - No memory is mutated - this is a pure comparison
 ///
 /// # Implementation Notes
@@ -111,20 +338,1144 @@ unsafe extern "C" {
 ///
 /// This eliminates the overhead of Rust's slice comparison and provides
 /// direct control over the BPF instruction sequence.
+///
+/// With the `branchless` feature enabled, this function dispatches to
+/// [`fast_eq_branchless`] instead. With `opt-size`, the loop-based encoding
+/// in `src/asm/opt-size/` is assembled in place of the unrolled routine.
 #[inline(always)]
 pub fn fast_eq<T>(lhs: &T, rhs: &T) -> bool
 where
-    T: AsRef<[u8]> + PartialEq,
+    T: Key,
+{
+    #[cfg(feature = "branchless")]
+    {
+        fast_eq_branchless(lhs, rhs)
+    }
+
+    #[cfg(not(feature = "branchless"))]
+    {
+        fast_eq_early_exit(lhs, rhs)
+    }
+}
+
+/// Compares the leading 32 bytes of two byte slices at any alignment
+///
+/// For keys at odd offsets inside packed account data, sliced straight out
+/// of the data: `fast_eq_unaligned(&data[13..], &other[41..])`. Returns
+/// `false` if either slice is shorter than 32 bytes.
+///
+/// This uses the same assembly as [`fast_eq`]. sBPF loads have no alignment
+/// requirement, so `ldxdw` already reads a word from any address at full
+/// speed, and a byte-granular routine would only cost more CU. Natively the
+/// bytes are read with unaligned loads.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_unaligned;
+///
+/// let mut data = [0u8; 80];
+/// data[13..45].copy_from_slice(&[7u8; 32]);
+///
+/// assert!(fast_eq_unaligned(&data[13..], &[7u8; 32]));
+/// assert!(!fast_eq_unaligned(&data[60..], &[7u8; 32]));
+/// ```
+#[inline(always)]
+pub fn fast_eq_unaligned(lhs: &[u8], rhs: &[u8]) -> bool {
+    // SAFETY: both slices hold at least 32 bytes.
+    lhs.len() >= 32 && rhs.len() >= 32 && unsafe { eq_raw(lhs.as_ptr(), rhs.as_ptr()) }
+}
+
+/// [`fast_eq`] over two different [`Key`] types
+///
+/// Compares a `Pubkey` against a byte constant, or a word-array field
+/// against a `Pubkey`, without converting either side first:
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq2;
+/// use solana_program::pubkey::Pubkey;
+///
+/// const EXPECTED: [u8; 32] = [7u8; 32];
+/// let authority = Pubkey::new_from_array([7u8; 32]);
+/// assert!(fast_eq2(&authority, &EXPECTED));
+/// ```
+///
+/// Both sides are viewed as their 32 key bytes, so this runs the same
+/// routine as [`fast_eq`]. For accounts and Anchor wrappers, see
+/// [`same_key`].
+#[inline(always)]
+pub fn fast_eq2<L: Key, R: Key>(lhs: &L, rhs: &R) -> bool {
+    fast_eq(lhs.key_bytes(), rhs.key_bytes())
+}
+
+/// Compares two optional keys: equal if both are `None`, or both are `Some`
+/// with equal keys
+///
+/// The tags are checked first, so [`fast_eq`] only runs when both sides hold
+/// a key. Suited to optional authority fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_opt;
+///
+/// assert!(fast_eq_opt::<[u8; 32]>(&None, &None));
+/// assert!(fast_eq_opt(&Some([1u8; 32]), &Some([1u8; 32])));
+/// assert!(!fast_eq_opt(&Some([1u8; 32]), &None));
+/// ```
+#[inline(always)]
+pub fn fast_eq_opt<T: Key>(lhs: &Option<T>, rhs: &Option<T>) -> bool {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => fast_eq(lhs, rhs),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+/// Returns `true` if `lhs` is `Some` and equals `rhs`
+///
+/// An unset optional authority never matches.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::opt_is;
+///
+/// assert!(opt_is(&Some([1u8; 32]), &[1u8; 32]));
+/// assert!(!opt_is(&None, &[1u8; 32]));
+/// ```
+#[inline(always)]
+pub fn opt_is<T: Key>(lhs: &Option<T>, rhs: &T) -> bool {
+    matches!(lhs, Some(lhs) if fast_eq(lhs, rhs))
+}
+
+/// Compares the keys of two [`KeyOf`] values, which need not be the same type
+///
+/// Accepts accounts and bare keys alike, comparing through [`fast_eq`]:
+///
+/// ```rust
+/// use solana_pubkey_compare::same_key;
+/// use solana_program::pubkey::Pubkey;
+///
+/// let authority = Pubkey::new_from_array([7u8; 32]);
+/// assert!(same_key(&authority, &[7u8; 32]));
+/// ```
+#[inline(always)]
+pub fn same_key<A: KeyOf, B: KeyOf>(lhs: &A, rhs: &B) -> bool {
+    fast_eq(lhs.key_of().key_bytes(), rhs.key_of().key_bytes())
+}
+
+/// Early-exit comparison backing [`fast_eq`], independent of the
+/// `branchless` feature so `fast_eq_hint` can always reach it
+#[cfg_attr(feature = "branchless", allow(dead_code))]
+#[inline(always)]
+fn fast_eq_early_exit<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
 {
     #[cfg(target_os = "solana")]
     unsafe {
-        __solana_pubkey_compare__fast_eq(
-            lhs as *const _ as *const u8,
-            rhs as *const _ as *const u8)
+        __solana_pubkey_compare__fast_eq(lhs.key_ptr(), rhs.key_ptr())
     }
 
     #[cfg(not(target_os = "solana"))]
     {
-        lhs == rhs
+        lhs.key_bytes() == rhs.key_bytes()
+    }
+}
+
+/// Early-exit comparison of two 32-byte regions given only raw pointers
+///
+/// Never materializes a reference to either region, so it is sound to call
+/// while the bytes are also reachable through a live `&mut` borrow.
+///
+/// # Safety
+///
+/// Both pointers must be valid for reads of 32 bytes, with no concurrent
+/// writes for the duration of the call.
+#[inline(always)]
+pub(crate) unsafe fn eq_raw(lhs: *const u8, rhs: *const u8) -> bool {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_eq(lhs, rhs)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    unsafe {
+        core::ptr::read_unaligned(lhs as *const [u8; 32])
+            == core::ptr::read_unaligned(rhs as *const [u8; 32])
+    }
+}
+
+/// Public key equality for callers that only hold raw pointers
+///
+/// For zero-copy frameworks that address account data through pointers: no
+/// [`Key`] bound, and no reference to either key is created. Runs the same
+/// routine as [`fast_eq`]; the pointers need no particular alignment.
+///
+/// # Safety
+///
+/// Both pointers must be valid for reads of 32 bytes, with no concurrent
+/// writes for the duration of the call.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_raw;
+///
+/// let data = [[4u8; 32], [4u8; 32]];
+/// let base = data.as_ptr() as *const u8;
+/// assert!(unsafe { fast_eq_raw(base, base.add(32)) });
+/// ```
+#[inline(always)]
+pub unsafe fn fast_eq_raw(lhs: *const u8, rhs: *const u8) -> bool {
+    // SAFETY: forwarded from the caller.
+    unsafe { eq_raw(lhs, rhs) }
+}
+
+/// Branchless public key equality comparison
+///
+/// Instead of exiting on the first mismatching 8-byte chunk, the assembly
+/// XORs all four word pairs, ORs the differences together and folds the
+/// accumulator into a boolean with no conditional jumps at all.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: a fixed 21-instruction body for every input
+/// - **Early-exit `fast_eq`**: 5 to 14 instructions depending on where the
+///   first difference is
+///
+/// sBPF meters one compute unit per executed instruction and charges nothing
+/// extra for taken branches, so this routine never uses *fewer* CU than
+/// [`fast_eq`]. Its gap is smallest when keys usually match (14 vs 21) and
+/// largest when they usually differ early. Prefer it where a constant,
+/// input-independent cost matters more than the minimum cost.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_branchless;
+///
+/// let array1 = [7u8; 32];
+/// let array2 = [7u8; 32];
+/// assert!(fast_eq_branchless(&array1, &array2));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_eq_branchless.s`. Enable the `branchless` feature
+/// to make [`fast_eq`] use this routine crate-wide.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_branchless<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_eq_branchless(lhs.key_ptr(), rhs.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.key_bytes() == rhs.key_bytes()
+    }
+}
+
+/// Branchless public key equality returning `1` or `0` as a `u64`
+///
+/// The [`fast_eq_branchless`] routine without the conversion to `bool`, so
+/// the result can feed arithmetic directly, e.g. a masked select:
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_u64;
+///
+/// let (fee, discounted_fee) = (5_000u64, 2_500u64);
+/// let is_partner = fast_eq_u64(&[4u8; 32], &[4u8; 32]);
+/// let mask = is_partner.wrapping_neg(); // all ones if equal
+/// assert_eq!((discounted_fee & mask) | (fee & !mask), 2_500);
+/// ```
+///
+/// Deriving the value from a `bool` instead can make LLVM emit branches on
+/// the BPF side; this is the same 21-instruction body, exported under a
+/// second symbol with a `u64` return.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_u64<T>(lhs: &T, rhs: &T) -> u64
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_eq_u64(lhs.key_ptr(), rhs.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        (lhs.key_bytes() == rhs.key_bytes()) as u64
+    }
+}
+
+/// Constant-time public key equality
+///
+/// Every word of both keys is loaded, XORed and ORed into one accumulator,
+/// with no data-dependent branches on any target:
+///
+/// - **On Solana BPF**: the [`fast_eq_branchless`] routine, a fixed 21
+///   instructions, so CU consumption never depends on the keys
+/// - **On native**: the same fold in Rust, with the accumulator hidden from
+///   the optimizer so it cannot reintroduce an early exit. Off-chain signer
+///   tooling can use it where comparison timing must not leak key bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::ct_eq;
+///
+/// assert!(ct_eq(&[1u8; 32], &[1u8; 32]));
+/// assert!(!ct_eq(&[1u8; 32], &[2u8; 32]));
+/// ```
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn ct_eq<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    {
+        fast_eq_branchless(lhs, rhs)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let (lhs, rhs) = (lhs.key_bytes(), rhs.key_bytes());
+        let word =
+            |key: &[u8; 32], at: usize| u64::from_ne_bytes(key[at..at + 8].try_into().unwrap());
+        let mut diff = 0;
+        for at in [0, 8, 16, 24] {
+            diff |= word(lhs, at) ^ word(rhs, at);
+        }
+        core::hint::black_box(diff) == 0
+    }
+}
+
+/// Public key inequality: `true` if the keys differ
+///
+/// Equivalent to `!fast_eq(lhs, rhs)`, but backed by its own assembly that
+/// returns `true` straight from the first mismatching 8-byte chunk, so guard
+/// clauses such as "reject if key != expected" need no negation.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 5 instructions when the first 8 bytes differ, up to
+///   14 when the keys match, the same profile as early-exit [`fast_eq`]
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_ne;
+///
+/// let expected = [5u8; 32];
+/// let provided = [6u8; 32];
+/// assert!(fast_ne(&expected, &provided));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_ne.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_ne<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_ne(lhs.key_ptr(), rhs.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.key_bytes() != rhs.key_bytes()
+    }
+}
+
+/// Lexicographic ordering of two public keys, matching `Pubkey`'s `Ord`
+///
+/// Keys are ordered by their bytes, most significant first. The assembly
+/// locates the first differing 8-byte chunk with the early-exit chain of
+/// [`fast_eq`] and byte-swaps only that pair, so keeping a sorted key array
+/// on-chain does not pay for a general slice comparison.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 8 or 9 instructions when the first 8 bytes differ,
+///   14 for equal keys, at most 18
+///
+/// # Examples
+///
+/// ```rust
+/// use core::cmp::Ordering;
+/// use solana_pubkey_compare::fast_cmp;
+///
+/// let mut low = [9u8; 32];
+/// low[31] = 0;
+/// assert_eq!(fast_cmp(&low, &[9u8; 32]), Ordering::Less);
+/// assert_eq!(fast_cmp(&[9u8; 32], &[9u8; 32]), Ordering::Equal);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_cmp.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_cmp<T>(lhs: &T, rhs: &T) -> core::cmp::Ordering
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        // SAFETY: the routine returns -1, 0 or 1, the discriminants of
+        // `Ordering`'s `#[repr(i8)]` variants.
+        core::mem::transmute::<i8, core::cmp::Ordering>(__solana_pubkey_compare__fast_cmp(
+            lhs.key_ptr(),
+            rhs.key_ptr(),
+        ))
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.key_bytes().cmp(rhs.key_bytes())
+    }
+}
+
+/// Returns `true` if `lhs` orders strictly before `rhs`
+///
+/// Shorthand for `fast_cmp(lhs, rhs).is_lt()`, exiting on the first
+/// differing 8-byte chunk. AMM-style programs use it to put a mint pair in
+/// canonical order:
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_lt;
+///
+/// let (mint_x, mint_y) = ([8u8; 32], [3u8; 32]);
+/// let (mint_a, mint_b) = if fast_lt(&mint_x, &mint_y) {
+///     (mint_x, mint_y)
+/// } else {
+///     (mint_y, mint_x)
+/// };
+/// assert_eq!((mint_a, mint_b), ([3u8; 32], [8u8; 32]));
+/// ```
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_lt<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
+{
+    fast_cmp(lhs, rhs).is_lt()
+}
+
+/// Returns `true` if `lhs` orders strictly after `rhs`
+///
+/// Shorthand for `fast_cmp(lhs, rhs).is_gt()`; see [`fast_lt`].
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_gt<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
+{
+    fast_cmp(lhs, rhs).is_gt()
+}
+
+/// Returns `true` if every byte of `key` is zero, i.e. it equals
+/// `Pubkey::default()`
+///
+/// The usual test for an unset key field. The assembly compares each 8-byte
+/// chunk against an immediate zero, so no zero key is materialized and only
+/// one key is loaded.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 4 instructions when the first 8 bytes are non-zero,
+///   10 for the zero key
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_is_zero;
+///
+/// assert!(fast_is_zero(&[0u8; 32]));
+/// assert!(!fast_is_zero(&[1u8; 32]));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_is_zero.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_is_zero<T>(key: &T) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_is_zero(key.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        key.key_bytes() == &[0; 32]
+    }
+}
+
+/// Equality of two 64-byte values such as ed25519 signatures
+///
+/// Runs the early-exit chain of [`fast_eq`] over eight 8-byte chunks in a
+/// single call, instead of comparing two 32-byte halves. A `Signature` can be
+/// passed as `<&[u8; 64]>::try_from(signature.as_ref())`.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 5 instructions when the first 8 bytes differ, up to
+///   26 when the values match
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_64;
+///
+/// let stored = [6u8; 64];
+/// let mut provided = [6u8; 64];
+/// assert!(fast_eq_64(&stored, &provided));
+/// provided[63] = 0;
+/// assert!(!fast_eq_64(&stored, &provided));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_eq_64.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_64(lhs: &[u8; 64], rhs: &[u8; 64]) -> bool {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_eq_64(lhs.as_ptr(), rhs.as_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs == rhs
+    }
+}
+
+/// Equality of two 8-byte values such as Anchor discriminators
+///
+/// Both values are compared with one 64-bit load each, where a slice
+/// comparison would call `memcmp`. Discriminator checks run on every
+/// instruction, so this is the cheapest way to dispatch or validate them.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 5 instructions when equal, 6 when different
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_8;
+///
+/// const DISCRIMINATOR: [u8; 8] = [24, 30, 200, 40, 5, 28, 7, 119];
+/// let data = [24, 30, 200, 40, 5, 28, 7, 119, 1, 2, 3];
+///
+/// assert!(fast_eq_8(&DISCRIMINATOR, data[..8].try_into().unwrap()));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_eq_8.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_8(lhs: &[u8; 8], rhs: &[u8; 8]) -> bool {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_eq_8(lhs.as_ptr(), rhs.as_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs == rhs
+    }
+}
+
+/// Equality of two `N`-byte values through the assembly body for `N`
+///
+/// One entry point for discriminators (8), truncated hashes (16), keys (32),
+/// signatures (64) and signature pairs (128). Each size has its own unrolled
+/// early-exit routine, selected at compile time; any other `N` fails to
+/// compile.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 5 instructions when the first 8 bytes differ, up to
+///   `3 * N / 8 + 2` when the values match
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_n;
+///
+/// assert!(fast_eq_n(&[1u8; 16], &[1u8; 16]));
+/// assert!(!fast_eq_n(&[1u8; 128], &[2u8; 128]));
+/// ```
+///
+/// ```compile_fail
+/// use solana_pubkey_compare::fast_eq_n;
+///
+/// // No routine for 24-byte values
+/// fast_eq_n(&[0u8; 24], &[0u8; 24]);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_eq_{N}.s`, and `src/asm/cmp_pubkey_eq.s` for `N = 32`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_n<const N: usize>(lhs: &[u8; N], rhs: &[u8; N]) -> bool {
+    const {
+        assert!(
+            matches!(N, 8 | 16 | 32 | 64 | 128),
+            "fast_eq_n supports N = 8, 16, 32, 64 and 128"
+        )
+    };
+
+    #[cfg(target_os = "solana")]
+    unsafe {
+        let (lhs, rhs) = (lhs.as_ptr(), rhs.as_ptr());
+        match N {
+            8 => __solana_pubkey_compare__fast_eq_8(lhs, rhs),
+            16 => __solana_pubkey_compare__fast_eq_16(lhs, rhs),
+            32 => __solana_pubkey_compare__fast_eq(lhs, rhs),
+            64 => __solana_pubkey_compare__fast_eq_64(lhs, rhs),
+            _ => __solana_pubkey_compare__fast_eq_128(lhs, rhs),
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs == rhs
+    }
+}
+
+/// Returns `true` if `key` equals `a` or `b`
+///
+/// One call in place of `fast_eq(key, a) || fast_eq(key, b)`, for checks
+/// such as "is this the token program or the token-2022 program". When `a`
+/// fails on the first 8 bytes, the key chunk already loaded is reused for
+/// `b`, which is the usual case since distinct candidates rarely share a
+/// prefix.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 14 instructions when `key == a`, 16 when `key == b`,
+///   7 when the key differs from both in its first 8 bytes; at most 26
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::eq_either;
+///
+/// let (token, token_2022) = ([6u8; 32], [7u8; 32]);
+/// assert!(eq_either(&[7u8; 32], &token, &token_2022));
+/// assert!(!eq_either(&[8u8; 32], &token, &token_2022));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_eq_either.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn eq_either<T>(key: &T, a: &T, b: &T) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__eq_either(key.key_ptr(), a.key_ptr(), b.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        key.key_bytes() == a.key_bytes() || key.key_bytes() == b.key_bytes()
+    }
+}
+
+/// Returns `true` if `a1 == b1` and `a2 == b2`
+///
+/// Both comparisons run in one assembly call, so the "check mint and owner"
+/// pattern of token programs pays the call overhead once. The second pair is
+/// not read if the first differs.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 5 instructions when the first pair differs in its
+///   first 8 bytes, 26 when both pairs match
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::eq_pairs;
+///
+/// let (mint, owner) = ([1u8; 32], [2u8; 32]);
+/// assert!(eq_pairs(&mint, &[1u8; 32], &owner, &[2u8; 32]));
+/// assert!(!eq_pairs(&mint, &[1u8; 32], &owner, &[3u8; 32]));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_eq_pairs.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn eq_pairs<T, U>(a1: &T, b1: &T, a2: &U, b2: &U) -> bool
+where
+    T: Key,
+    U: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__eq_pairs(a1.key_ptr(), b1.key_ptr(), a2.key_ptr(), b2.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        a1.key_bytes() == b1.key_bytes() && a2.key_bytes() == b2.key_bytes()
+    }
+}
+
+/// Returns `true` if the first `n` bytes of two keys are equal
+///
+/// For vanity-prefix checks and bucketing schemes keyed on a key's leading
+/// bytes. `n = 0` is always `true`; `n` above 32 compares the whole key.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 4 instructions per whole 8-byte chunk of the prefix,
+///   plus 17 for a trailing partial chunk; 18 for `n = 32`, at most 30
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::prefix_eq;
+///
+/// let mut key = [0xAAu8; 32];
+/// key[5] = 0;
+/// assert!(prefix_eq(&key, &[0xAAu8; 32], 5));
+/// assert!(!prefix_eq(&key, &[0xAAu8; 32], 6));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_prefix_eq.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn prefix_eq<T>(lhs: &T, rhs: &T, n: usize) -> bool
+where
+    T: Key,
+{
+    let n = n.min(32);
+
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__prefix_eq(lhs.key_ptr(), rhs.key_ptr(), n as u64)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.key_bytes()[..n] == rhs.key_bytes()[..n]
+    }
+}
+
+/// Returns `true` if two keys are equal on the bits set in `mask`
+///
+/// Bits cleared in the mask are "don't care" and never affect the result,
+/// for addressing schemes that match keys modulo a few bytes. An all-ones
+/// mask is plain equality; an all-zero mask matches everything.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 8 instructions when the first 8 bytes differ under
+///   the mask, 26 when the keys match
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::masked_eq;
+///
+/// // Ignore the final byte
+/// let mut mask = [0xFFu8; 32];
+/// mask[31] = 0;
+///
+/// let mut key = [4u8; 32];
+/// key[31] = 9;
+/// assert!(masked_eq(&key, &[4u8; 32], &mask));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_masked_eq.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn masked_eq<T>(lhs: &T, rhs: &T, mask: &[u8; 32]) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__masked_eq(lhs.key_ptr(), rhs.key_ptr(), mask.as_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.key_bytes()
+            .iter()
+            .zip(rhs.key_bytes())
+            .zip(mask)
+            .all(|((lhs, rhs), mask)| (lhs ^ rhs) & mask == 0)
+    }
+}
+
+/// Returns the index of the first byte at which two keys differ, or `None`
+/// if they are equal
+///
+/// A failed equality check can log this one number instead of both keys.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 18 instructions for equal keys, 9 when byte 0
+///   differs, plus 6 for each equal byte before the difference inside its
+///   8-byte chunk
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::diff_index;
+///
+/// let mut key = [1u8; 32];
+/// key[20] = 0;
+/// assert_eq!(diff_index(&key, &[1u8; 32]), Some(20));
+/// assert_eq!(diff_index(&key, &key), None);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_diff_index.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn diff_index<T>(lhs: &T, rhs: &T) -> Option<usize>
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    {
+        let index = unsafe { __solana_pubkey_compare__diff_index(lhs.key_ptr(), rhs.key_ptr()) };
+        (index < 32).then_some(index as usize)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.key_bytes()
+            .iter()
+            .zip(rhs.key_bytes())
+            .position(|(lhs, rhs)| lhs != rhs)
+    }
+}
+
+/// Returns which 8-byte lane of two keys differs first: `0..=3`, or `4` if
+/// the keys are equal
+///
+/// A cheaper diagnostic than [`diff_index`] when the lane is enough: it
+/// never searches inside the lane, and the result fits a one-byte log field.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 5 instructions when lane 0 differs, 18 for equal keys
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::first_diff_qword;
+///
+/// let mut key = [1u8; 32];
+/// key[20] = 0;
+/// assert_eq!(first_diff_qword(&key, &[1u8; 32]), 2);
+/// assert_eq!(first_diff_qword(&key, &key), 4);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_first_diff_qword.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn first_diff_qword<T>(lhs: &T, rhs: &T) -> u8
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__first_diff_qword(lhs.key_ptr(), rhs.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        let (lhs, rhs) = (lhs.key_bytes(), rhs.key_bytes());
+        (0..4)
+            .find(|lane| lhs[lane * 8..][..8] != rhs[lane * 8..][..8])
+            .unwrap_or(4) as u8
+    }
+}
+
+/// Public key equality scanning from the last 8 bytes to the first
+///
+/// Exits on the first mismatching chunk like [`fast_eq`], but starts at the
+/// end of the key. Where keys share a long prefix and differ in their
+/// trailing bytes (vanity keys, fixed-prefix identifiers), this reaches the
+/// difference on the first compare. Also selected by
+/// [`Hint::DiffersAtEnd`].
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 5 instructions when the last 8 bytes differ, up to
+///   14 when the keys match
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_rev;
+///
+/// let mut key = [7u8; 32];
+/// key[31] = 0;
+/// assert!(!fast_eq_rev(&key, &[7u8; 32]));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_eq_rev.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_rev<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_eq_rev(lhs.key_ptr(), rhs.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.key_bytes() == rhs.key_bytes()
+    }
+}
+
+/// Asserts two keys are equal, aborting the program from assembly otherwise
+///
+/// The comparison and the `abort` syscall are fused in one routine, so the
+/// caller has no branch on a result and no panic machinery is linked for the
+/// failure path. The transaction fails without a message or source location;
+/// use `assert_keys_eq` (with the `solana-program` feature) when the logs
+/// should say which check failed.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 13 instructions when the keys match
+///
+/// # Panics
+///
+/// Off-chain, panics if the keys differ.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_assert_eq;
+///
+/// fast_assert_eq(&[3u8; 32], &[3u8; 32]);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/assert_pubkey_eq.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+#[track_caller]
+pub fn fast_assert_eq<T>(lhs: &T, rhs: &T)
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_assert_eq(lhs.key_ptr(), rhs.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        assert!(lhs.key_bytes() == rhs.key_bytes(), "fast_assert_eq failed")
+    }
+}
+
+/// [`fast_assert_eq`] that logs `code` before aborting
+///
+/// On mismatch the routine logs `code` via `sol_log_64` (as
+/// `Program log: 0x<code>, 0x0, 0x0, 0x0, 0x0`) and then aborts, so each
+/// call site can be identified from the transaction logs. The transaction
+/// still fails with an abort rather than `ProgramError::Custom(code)`: only
+/// the entrypoint's return value sets the program error, and a routine deep
+/// in the call stack cannot return it. When the error code itself must
+/// reach the client, use `require_keys_eq` with `FailureMode::Error`.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 13 instructions when the keys match
+///
+/// # Panics
+///
+/// Off-chain, panics with `code` in the message if the keys differ.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_assert_eq_with_code;
+///
+/// fast_assert_eq_with_code(&[3u8; 32], &[3u8; 32], 6001);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/assert_pubkey_eq.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+#[track_caller]
+pub fn fast_assert_eq_with_code<T>(lhs: &T, rhs: &T, code: u64)
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_assert_eq_with_code(lhs.key_ptr(), rhs.key_ptr(), code)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        assert!(
+            lhs.key_bytes() == rhs.key_bytes(),
+            "fast_assert_eq_with_code failed: code {code}"
+        )
+    }
+}
+
+/// Public key equality that returns immediately for two references to the
+/// same key
+///
+/// Compares the addresses first, so checking an account key against itself,
+/// as generated validation code often does, costs no loads. Otherwise this
+/// is [`fast_eq`] plus one instruction.
+///
+/// # Performance
+///
+/// - **On Solana BPF**: 3 instructions for the same address, 6 when the
+///   first 8 bytes differ, up to 15 for distinct copies of the same key
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_ptrcheck;
+///
+/// let key = [5u8; 32];
+/// assert!(fast_eq_ptrcheck(&key, &key));
+/// assert!(!fast_eq_ptrcheck(&key, &[6u8; 32]));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/cmp_pubkey_eq_ptrcheck.s`.
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_ptrcheck<T>(lhs: &T, rhs: &T) -> bool
+where
+    T: Key,
+{
+    #[cfg(target_os = "solana")]
+    unsafe {
+        __solana_pubkey_compare__fast_eq_ptrcheck(lhs.key_ptr(), rhs.key_ptr())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        core::ptr::eq(lhs, rhs) || lhs.key_bytes() == rhs.key_bytes()
+    }
+}
+
+/// Expected outcome of a comparison at a particular call site
+///
+/// Passed to [`fast_eq_hint`] to pick the routine whose cost profile suits
+/// the data seen at that call site.
+#[cfg(feature = "cmp")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hint {
+    /// The keys almost always match (e.g. validating a stored authority).
+    ///
+    /// Selects the early-exit routine: 14 instructions when the keys match,
+    /// still fewer than any branchless encoding.
+    UsuallyEqual,
+    /// The keys usually differ (e.g. scanning a list for one entry).
+    ///
+    /// Selects the early-exit routine: 5 instructions when the first 8 bytes
+    /// differ, up to 14 when the keys match.
+    UsuallyDifferent,
+    /// The keys usually differ, but share a long prefix (e.g. vanity keys).
+    ///
+    /// Selects [`fast_eq_rev`]: 5 instructions when the last 8 bytes differ,
+    /// up to 14 when the keys match.
+    DiffersAtEnd,
+    /// The cost must not depend on the keys, whatever they hold.
+    ///
+    /// Selects [`fast_eq_branchless`]: a fixed 21 instructions for every
+    /// input, at least 7 more than early exit.
+    ConstantCost,
+}
+
+/// Public key equality with a per-call-site algorithm choice
+///
+/// Unlike the crate-wide `branchless` feature, the hint is chosen at each
+/// call site. It is resolved at compile time whenever `hint` is a constant,
+/// so no dispatch cost is added.
+///
+/// # Choosing a Hint
+///
+/// Early exit is never slower in CU than the branchless routine (sBPF charges
+/// one CU per instruction, branch or not), so both [`Hint::UsuallyEqual`] and
+/// [`Hint::UsuallyDifferent`] select it; the distinction records intent at
+/// the call site. On matching keys the gap is at its smallest, 14 vs 21
+/// instructions. Use [`Hint::ConstantCost`] only where a cost that does not
+/// depend on the data is worth those extra units.
+/// The `bench` program in this repository exposes one opcode per hint so the
+/// difference can be measured for a specific workload.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::{fast_eq_hint, Hint};
+///
+/// let stored = [3u8; 32];
+/// let provided = [3u8; 32];
+/// assert!(fast_eq_hint(&stored, &provided, Hint::UsuallyEqual));
+/// ```
+#[cfg(feature = "cmp")]
+#[inline(always)]
+pub fn fast_eq_hint<T>(lhs: &T, rhs: &T, hint: Hint) -> bool
+where
+    T: Key,
+{
+    match hint {
+        Hint::UsuallyEqual | Hint::UsuallyDifferent => fast_eq_early_exit(lhs, rhs),
+        Hint::DiffersAtEnd => fast_eq_rev(lhs, rhs),
+        Hint::ConstantCost => fast_eq_branchless(lhs, rhs),
     }
 }
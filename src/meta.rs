@@ -0,0 +1,83 @@
+//! Account meta normalization for off-chain transaction builders
+//!
+//! Instructions assembled from several sources often name the same account
+//! more than once with different flags. [`sanitize_metas`] folds those into
+//! one meta per key and lays them out the way the runtime orders message
+//! accounts.
+
+use core::cmp::Ordering;
+
+use solana_program::instruction::AccountMeta;
+
+use crate::{fast_eq, sorted::order};
+
+/// Deduplicates `metas` by key and orders them canonically
+///
+/// Metas naming the same key are merged into one that is a signer if any of
+/// them was, and writable if any of them was. The result is ordered as the
+/// runtime lays out message accounts: writable signers, read-only signers,
+/// writable non-signers, then read-only non-signers, each group in order of
+/// first appearance, so a fee payer passed first stays first.
+///
+/// Duplicates are found by sorting on the key with the word-wise comparator
+/// rather than by hashing, `O(n log n)` in total.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+/// use solana_pubkey_compare::sanitize_metas;
+///
+/// let [payer, pool, oracle] = [(); 3].map(|_| Pubkey::new_unique());
+/// let mut metas = vec![
+///     AccountMeta::new_readonly(oracle, false),
+///     AccountMeta::new(payer, true),
+///     AccountMeta::new_readonly(pool, false),
+///     AccountMeta::new(pool, false),
+/// ];
+///
+/// sanitize_metas(&mut metas);
+/// assert_eq!(
+///     metas,
+///     [
+///         AccountMeta::new(payer, true),
+///         AccountMeta::new(pool, false),
+///         AccountMeta::new_readonly(oracle, false),
+///     ]
+/// );
+/// ```
+pub fn sanitize_metas(metas: &mut Vec<AccountMeta>) {
+    let mut indexed: Vec<(usize, AccountMeta)> = metas.drain(..).enumerate().collect();
+    // Stable, so each run of one key starts with its first appearance
+    indexed.sort_by(|(_, lhs), (_, rhs)| order(&lhs.pubkey, &rhs.pubkey));
+
+    let mut merged: Vec<(usize, AccountMeta)> = Vec::with_capacity(indexed.len());
+    for (index, meta) in indexed {
+        match merged.last_mut() {
+            Some((_, last)) if fast_eq(&last.pubkey, &meta.pubkey) => {
+                last.is_signer |= meta.is_signer;
+                last.is_writable |= meta.is_writable;
+            }
+            _ => merged.push((index, meta)),
+        }
+    }
+
+    merged.sort_unstable_by(|(lhs_index, lhs), (rhs_index, rhs)| {
+        match group(lhs).cmp(&group(rhs)) {
+            Ordering::Equal => lhs_index.cmp(rhs_index),
+            ordering => ordering,
+        }
+    });
+    metas.extend(merged.into_iter().map(|(_, meta)| meta));
+}
+
+/// Position of a meta's group in message account order
+#[inline(always)]
+fn group(meta: &AccountMeta) -> u8 {
+    match (meta.is_signer, meta.is_writable) {
+        (true, true) => 0,
+        (true, false) => 1,
+        (false, true) => 2,
+        (false, false) => 3,
+    }
+}
@@ -0,0 +1,158 @@
+//! Metaplex token metadata checks over raw account data
+//!
+//! Verifies a metadata account's address and its authority and collection
+//! fields without `mpl-token-metadata`. The update authority and mint sit at
+//! fixed offsets; the collection follows variable-length Borsh fields, which
+//! are skipped by their length prefixes rather than deserialized.
+//!
+//! ```rust,ignore
+//! if !is_metadata_pda(metadata.key, mint.key) {
+//!     return Err(ProgramError::InvalidSeeds);
+//! }
+//! let data = metadata.try_borrow_data()?;
+//! if !collection_is(&data, &COLLECTION_MINT, true) {
+//!     return Err(ProgramError::InvalidAccountData);
+//! }
+//! ```
+
+use solana_program::pubkey::Pubkey;
+
+use crate::{CompareError, Key, fast_eq, fast_eq_at};
+
+/// The Metaplex token metadata program
+pub const METADATA_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+/// Leading `Key` byte identifying a `MetadataV1` account
+pub const METADATA_V1_KEY: u8 = 4;
+
+/// Byte offset of the update authority
+pub const UPDATE_AUTHORITY_OFFSET: usize = 1;
+
+/// Byte offset of the mint
+pub const METADATA_MINT_OFFSET: usize = 33;
+
+/// Size of one `Creator` entry: address, verified flag and share
+const CREATOR_SIZE: usize = 34;
+
+/// Derives the metadata account address and bump for `mint`
+#[inline]
+pub fn metadata_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &METADATA_PROGRAM_ID,
+    )
+}
+
+/// Returns `true` if `metadata` is the metadata account address of `mint`
+///
+/// Runs the full bump search of `find_program_address`; programs that store
+/// the bump should derive with `create_program_address` instead.
+#[inline]
+pub fn is_metadata_pda(metadata: &Pubkey, mint: &Pubkey) -> bool {
+    fast_eq(&metadata_pda(mint).0, metadata)
+}
+
+/// Returns `true` if `data` is a metadata account whose update authority is
+/// `key`
+#[inline]
+pub fn update_authority_is<K: Key>(data: &[u8], key: &K) -> bool {
+    data.first() == Some(&METADATA_V1_KEY) && fast_eq_at(data, UPDATE_AUTHORITY_OFFSET, key)
+}
+
+/// Returns `true` if `data` is a metadata account for `mint`
+#[inline]
+pub fn metadata_mint_is<K: Key>(data: &[u8], mint: &K) -> bool {
+    data.first() == Some(&METADATA_V1_KEY) && fast_eq_at(data, METADATA_MINT_OFFSET, mint)
+}
+
+/// Returns the collection key and its verified flag, or `None` if the
+/// metadata names no collection
+///
+/// Fails with `LengthInvalid` if `data` is not a `MetadataV1` account or
+/// ends before the collection field.
+pub fn collection(data: &[u8]) -> Result<Option<(&[u8; 32], bool)>, CompareError> {
+    if data.first() != Some(&METADATA_V1_KEY) {
+        return Err(CompareError::LengthInvalid);
+    }
+    let mut cursor = Cursor {
+        data,
+        at: METADATA_MINT_OFFSET + 32,
+    };
+    // name, symbol, uri
+    for _ in 0..3 {
+        let len = cursor.u32()?;
+        cursor.skip(len as usize)?;
+    }
+    // seller_fee_basis_points
+    cursor.skip(2)?;
+    if cursor.option()? {
+        let creators = cursor.u32()?;
+        cursor.skip((creators as usize).saturating_mul(CREATOR_SIZE))?;
+    }
+    // primary_sale_happened, is_mutable
+    cursor.skip(2)?;
+    // edition_nonce, token_standard
+    for _ in 0..2 {
+        if cursor.option()? {
+            cursor.skip(1)?;
+        }
+    }
+    if !cursor.option()? {
+        return Ok(None);
+    }
+    let verified = cursor.take(1)?[0] != 0;
+    let key = cursor.take(32)?.try_into().unwrap();
+    Ok(Some((key, verified)))
+}
+
+/// Returns `true` if the metadata's collection is `key`, and, when
+/// `require_verified` is set, the collection is verified
+///
+/// Malformed data never matches.
+#[inline]
+pub fn collection_is<K: Key>(data: &[u8], key: &K, require_verified: bool) -> bool {
+    matches!(
+        collection(data),
+        Ok(Some((collection, verified)))
+            if (verified || !require_verified) && fast_eq(collection, key.key_bytes())
+    )
+}
+
+/// Forward-only reader over Borsh-encoded account data
+struct Cursor<'a> {
+    data: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CompareError> {
+        let end = self
+            .at
+            .checked_add(len)
+            .ok_or(CompareError::LengthInvalid)?;
+        let bytes = self
+            .data
+            .get(self.at..end)
+            .ok_or(CompareError::LengthInvalid)?;
+        self.at = end;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), CompareError> {
+        self.take(len).map(|_| ())
+    }
+
+    fn u32(&mut self) -> Result<u32, CompareError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    /// Reads a Borsh `Option` tag, returning whether a value follows
+    fn option(&mut self) -> Result<bool, CompareError> {
+        match self.take(1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(CompareError::LengthInvalid),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! SPL Token mint authority checks over raw account data
+//!
+//! Reads the `COption<Pubkey>` authorities of a Mint in place, so issuers can
+//! validate a mint without `Mint::unpack`. Token-2022 mints share the same
+//! base layout and extend it, so their data works too. Nothing here checks
+//! the account owner or `is_initialized`; callers must still verify the
+//! account is owned by the expected token program.
+
+use crate::{CompareError, Key, fast_eq};
+
+/// Size of the base SPL Token Mint layout
+pub const MINT_LEN: usize = 82;
+
+/// Byte offset of the mint authority's `COption` tag
+pub const MINT_AUTHORITY_OFFSET: usize = 0;
+
+/// Byte offset of the freeze authority's `COption` tag
+pub const FREEZE_AUTHORITY_OFFSET: usize = 46;
+
+/// Decodes the `COption<Pubkey>` at `offset` of a layout at least `min_len`
+/// bytes long: a 4-byte little-endian tag (0 = `None`, 1 = `Some`) followed
+/// by the key
+#[inline(always)]
+pub(crate) fn coption_key(
+    data: &[u8],
+    min_len: usize,
+    offset: usize,
+) -> Result<Option<&[u8; 32]>, CompareError> {
+    debug_assert!(offset + 36 <= min_len);
+    if data.len() < min_len {
+        return Err(CompareError::LengthInvalid);
+    }
+    let key = data[offset + 4..offset + 36].try_into().unwrap();
+    match data[offset..offset + 4] {
+        [0, 0, 0, 0] => Ok(None),
+        [1, 0, 0, 0] => Ok(Some(key)),
+        _ => Err(CompareError::LengthInvalid),
+    }
+}
+
+/// Returns `true` if the raw `COption<Pubkey>` in `raw` is `Some(key)`
+///
+/// `raw` is the 36-byte field as stored in SPL Token accounts and mints: a
+/// 4-byte little-endian tag followed by the key. A `None` or malformed tag
+/// never matches, and the key comparison only runs on a `Some` tag.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::mint::{MINT_LEN, coption_pubkey_eq};
+///
+/// let mut mint = [0u8; MINT_LEN];
+/// mint[46] = 1;
+/// mint[50..82].copy_from_slice(&[4u8; 32]);
+///
+/// assert!(coption_pubkey_eq(mint[46..82].try_into().unwrap(), &[4u8; 32]));
+/// ```
+#[inline(always)]
+pub fn coption_pubkey_eq<K: Key>(raw: &[u8; 36], key: &K) -> bool {
+    let (tag, body) = raw.split_at(4);
+    tag == [1, 0, 0, 0] && fast_eq(<&[u8; 32]>::try_from(body).unwrap(), key.key_bytes())
+}
+
+/// Returns the mint authority, or `None` if minting is disabled
+///
+/// Fails with `LengthInvalid` if `data` is shorter than [`MINT_LEN`] or the
+/// option tag is neither 0 nor 1.
+#[inline]
+pub fn mint_authority(data: &[u8]) -> Result<Option<&[u8; 32]>, CompareError> {
+    coption_key(data, MINT_LEN, MINT_AUTHORITY_OFFSET)
+}
+
+/// Returns the freeze authority, or `None` if the mint has none
+///
+/// Fails like [`mint_authority`].
+#[inline]
+pub fn freeze_authority(data: &[u8]) -> Result<Option<&[u8; 32]>, CompareError> {
+    coption_key(data, MINT_LEN, FREEZE_AUTHORITY_OFFSET)
+}
+
+/// Returns `true` if the mint authority is set and equals `key`
+///
+/// Malformed data never matches.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::mint::{MINT_LEN, mint_authority_is};
+///
+/// let mut mint = [0u8; MINT_LEN];
+/// mint[0] = 1;
+/// mint[4..36].copy_from_slice(&[9u8; 32]);
+///
+/// assert!(mint_authority_is(&mint, &[9u8; 32]));
+/// ```
+#[inline]
+pub fn mint_authority_is<K: Key>(data: &[u8], key: &K) -> bool {
+    matches!(mint_authority(data), Ok(Some(authority)) if fast_eq(authority, key.key_bytes()))
+}
+
+/// Returns `true` if the freeze authority is set and equals `key`
+///
+/// Malformed data never matches.
+#[inline]
+pub fn freeze_authority_is<K: Key>(data: &[u8], key: &K) -> bool {
+    matches!(freeze_authority(data), Ok(Some(authority)) if fast_eq(authority, key.key_bytes()))
+}
@@ -0,0 +1,90 @@
+//! Key scans across several account-data buffers
+//!
+//! Registries that outgrow one account are paginated: each page account
+//! repeats the same header and record layout. [`MultiBufferSearch`] runs
+//! [`scan_keys`] over the pages in order and remembers where the last match
+//! was, so repeated calls walk every match across all pages.
+
+use crate::{Key, scan_keys};
+
+/// A resumable [`scan_keys`] over a sequence of buffers with one layout
+///
+/// In every buffer, record `i` has its key at `start_offset + i * stride`.
+/// Matches are reported as `(buffer, record)` index pairs, in buffer order
+/// and then record order.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::MultiBufferSearch;
+///
+/// // 8-byte page header, then 40-byte records: 32-byte owner + 8-byte amount
+/// let mut page_a = vec![0u8; 8 + 40 * 2];
+/// let mut page_b = vec![0u8; 8 + 40 * 3];
+/// page_a[8..40].copy_from_slice(&[9u8; 32]);
+/// page_b[8 + 40 * 2..8 + 40 * 2 + 32].copy_from_slice(&[9u8; 32]);
+///
+/// let pages = [&page_a[..], &page_b[..]];
+/// let mut search = MultiBufferSearch::new(&pages, 8, 40);
+/// assert_eq!(search.find_next(&[9u8; 32]), Some((0, 0)));
+/// assert_eq!(search.find_next(&[9u8; 32]), Some((1, 2)));
+/// assert_eq!(search.find_next(&[9u8; 32]), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiBufferSearch<'a> {
+    buffers: &'a [&'a [u8]],
+    start_offset: usize,
+    stride: usize,
+    buffer: usize,
+    record: usize,
+}
+
+impl<'a> MultiBufferSearch<'a> {
+    /// Creates a search over `buffers`, starting at the first record of the
+    /// first buffer
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn new(buffers: &'a [&'a [u8]], start_offset: usize, stride: usize) -> Self {
+        assert!(stride != 0, "MultiBufferSearch: stride must be non-zero");
+        MultiBufferSearch {
+            buffers,
+            start_offset,
+            stride,
+            buffer: 0,
+            record: 0,
+        }
+    }
+
+    /// Finds the next record whose key field equals `key`
+    ///
+    /// The scan resumes just past the previous match, or at the start if
+    /// there was none, and moves on to the next buffer when one is
+    /// exhausted. Returns `None` once every buffer has been scanned; the
+    /// search then stays exhausted until [`reset`](Self::reset).
+    pub fn find_next<K: Key>(&mut self, key: &K) -> Option<(usize, usize)> {
+        while let Some(data) = self.buffers.get(self.buffer) {
+            let offset = self
+                .record
+                .checked_mul(self.stride)
+                .and_then(|skipped| skipped.checked_add(self.start_offset));
+            if let Some(found) = offset.and_then(|offset| scan_keys(data, offset, self.stride, key))
+            {
+                let record = self.record + found;
+                self.record = record + 1;
+                return Some((self.buffer, record));
+            }
+            self.buffer += 1;
+            self.record = 0;
+        }
+        None
+    }
+
+    /// Restarts the search at the first record of the first buffer
+    #[inline]
+    pub fn reset(&mut self) {
+        self.buffer = 0;
+        self.record = 0;
+    }
+}
@@ -0,0 +1,106 @@
+//! [`NonZeroPubkey`]: a key with a niche, so `Option` of it needs no tag
+
+use core::num::NonZeroU64;
+
+use crate::{Key, KeyOf, fast_eq};
+
+/// A 32-byte key known not to be the all-zero default
+///
+/// `Option<NonZeroPubkey>` is exactly 32 bytes, so account structs can
+/// express an optional authority without a tag byte or `COption`'s four-byte
+/// tag. Zero-initialized account data reads as `None`.
+///
+/// The niche is the key's final 8 bytes, which must be non-zero. That is a
+/// stronger condition than "not the all-zero key", but Rust cannot place a
+/// niche on a 32-byte value as a whole; keys derived from a hash or an
+/// ed25519 point end in eight zero bytes with probability 2^-64, so in
+/// practice only hand-written constants are affected. [`NonZeroPubkey::new`]
+/// rejects such keys rather than storing them.
+///
+/// The in-memory bytes are exactly the key bytes, with alignment 1, so the
+/// type can be placed at any offset in account data.
+#[derive(Clone, Copy)]
+#[repr(C, packed)]
+pub struct NonZeroPubkey {
+    head: [u8; 24],
+    tail: NonZeroU64,
+}
+
+impl NonZeroPubkey {
+    /// Wraps `key`, or returns `None` if its final 8 bytes are all zero,
+    /// which includes the all-zero default key
+    ///
+    /// The check is a single 64-bit zero test.
+    #[inline(always)]
+    pub fn new<K: Key>(key: &K) -> Option<Self> {
+        let bytes = key.key_bytes();
+        let (head, tail) = bytes.split_at(24);
+        Some(NonZeroPubkey {
+            head: head.try_into().unwrap(),
+            tail: NonZeroU64::new(u64::from_ne_bytes(tail.try_into().unwrap()))?,
+        })
+    }
+
+    /// Returns the key bytes
+    #[inline(always)]
+    pub fn key_bytes(&self) -> &[u8; 32] {
+        // SAFETY: `repr(C, packed)` lays out the 24 head bytes followed by
+        // the 8 tail bytes with no padding and alignment 1.
+        unsafe { &*(self as *const Self as *const [u8; 32]) }
+    }
+
+    /// Returns a copy of the key bytes
+    #[inline(always)]
+    pub fn to_bytes(&self) -> [u8; 32] {
+        *self.key_bytes()
+    }
+
+    /// Returns the key as a `Pubkey`
+    #[cfg(feature = "solana-pubkey")]
+    #[inline(always)]
+    pub fn as_pubkey(&self) -> &solana_pubkey::Pubkey {
+        // SAFETY: `Pubkey` is a `repr(transparent)` wrapper around `[u8; 32]`.
+        unsafe { &*(self as *const Self as *const solana_pubkey::Pubkey) }
+    }
+}
+
+impl KeyOf for NonZeroPubkey {
+    type Key = [u8; 32];
+
+    #[inline(always)]
+    fn key_of(&self) -> &[u8; 32] {
+        self.key_bytes()
+    }
+}
+
+impl PartialEq for NonZeroPubkey {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        fast_eq(self.key_bytes(), other.key_bytes())
+    }
+}
+
+impl Eq for NonZeroPubkey {}
+
+impl core::fmt::Debug for NonZeroPubkey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("NonZeroPubkey")
+            .field(self.key_bytes())
+            .finish()
+    }
+}
+
+impl From<NonZeroPubkey> for [u8; 32] {
+    #[inline(always)]
+    fn from(key: NonZeroPubkey) -> Self {
+        key.to_bytes()
+    }
+}
+
+#[cfg(feature = "solana-pubkey")]
+impl From<NonZeroPubkey> for solana_pubkey::Pubkey {
+    #[inline(always)]
+    fn from(key: NonZeroPubkey) -> Self {
+        solana_pubkey::Pubkey::new_from_array(key.to_bytes())
+    }
+}
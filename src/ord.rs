@@ -0,0 +1,111 @@
+//! [`FastOrd`]: a key wrapper ordered by the chunked comparator
+
+use core::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::{Key, fast_eq};
+
+/// A key whose `Ord` compares 8-byte chunks instead of single bytes
+///
+/// Ordered collections such as `BTreeMap` compare keys at every node, and
+/// `Pubkey`'s derived `Ord` is a byte-wise slice comparison. Wrapping the key
+/// as `BTreeMap<FastOrd<Pubkey>, V>` keeps the same byte order but resolves
+/// each comparison on the first differing big-endian word, and on Solana
+/// through the `fast_cmp` assembly routine (with the `cmp` feature).
+///
+/// `FastOrd<T>` is `repr(transparent)`, so it is itself a [`Key`], and
+/// [`FastOrd::from_ref`] turns a borrowed key into a lookup key without
+/// copying it.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::BTreeMap;
+///
+/// use solana_pubkey_compare::FastOrd;
+///
+/// let mut balances = BTreeMap::new();
+/// balances.insert(FastOrd([2u8; 32]), 20u64);
+/// balances.insert(FastOrd([1u8; 32]), 10u64);
+///
+/// assert_eq!(balances.get(FastOrd::from_ref(&[2u8; 32])), Some(&20));
+/// assert_eq!(balances.keys().next(), Some(&FastOrd([1u8; 32])));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+pub struct FastOrd<T>(pub T);
+
+// SAFETY: `repr(transparent)` over a `Key` has the key's exact layout.
+unsafe impl<T: Key> Key for FastOrd<T> {}
+
+impl<T> FastOrd<T> {
+    /// Returns the wrapped key
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Views a borrowed key as a `FastOrd`, for map lookups
+    #[inline(always)]
+    pub fn from_ref(key: &T) -> &Self {
+        // SAFETY: `Self` is a `repr(transparent)` wrapper around `T`.
+        unsafe { &*(key as *const T as *const Self) }
+    }
+}
+
+impl<T> Deref for FastOrd<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for FastOrd<T> {
+    #[inline(always)]
+    fn from(key: T) -> Self {
+        FastOrd(key)
+    }
+}
+
+impl<T: Key> PartialEq for FastOrd<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        fast_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: Key> Eq for FastOrd<T> {}
+
+impl<T: Key> PartialOrd for FastOrd<T> {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Key> Ord for FastOrd<T> {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        #[cfg(all(feature = "cmp", target_os = "solana"))]
+        {
+            crate::fast_cmp(&self.0, &other.0)
+        }
+
+        #[cfg(not(all(feature = "cmp", target_os = "solana")))]
+        {
+            crate::set::key_cmp(&self.0, &other.0)
+        }
+    }
+}
+
+impl<T: Key> Hash for FastOrd<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.key_bytes().hash(state);
+    }
+}
@@ -0,0 +1,74 @@
+//! Boundary parsing of key lists from untrusted instruction data
+//!
+//! Programs commonly receive a list of keys packed back to back in their
+//! instruction data. The helpers here validate the length (and, for
+//! word-array key types, the alignment) once and reinterpret the bytes as a
+//! typed key slice, ready to hand to the comparison routines without copying
+//! or per-key `try_into` calls.
+
+use crate::Key;
+
+/// Reasons a byte buffer cannot be viewed as a list of keys
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The buffer holds fewer bytes than `count` keys require
+    TooShort {
+        /// Bytes needed for the requested keys
+        expected: usize,
+        /// Bytes actually available
+        actual: usize,
+    },
+    /// The buffer does not start on the alignment the key type requires
+    Misaligned,
+}
+
+/// Views the first `count * 32` bytes of `data` as `count` keys
+///
+/// Trailing bytes after the keys are ignored; use [`split_keys`] to keep
+/// them.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::{fast_eq, parse};
+///
+/// let mut data = [0u8; 65];
+/// data[32..64].copy_from_slice(&[7u8; 32]);
+///
+/// let keys = parse::keys_from_instruction_data(&data, 2).unwrap();
+/// assert!(fast_eq(&keys[1], &[7u8; 32]));
+/// ```
+#[inline]
+pub fn keys_from_instruction_data(data: &[u8], count: usize) -> Result<&[[u8; 32]], ParseError> {
+    split_keys(data, count).map(|(keys, _)| keys)
+}
+
+/// Splits `data` into `count` leading keys and the remaining bytes
+///
+/// Generic over the key type: for `[u8; 32]` and `Pubkey` any address is
+/// accepted, while word-array keys such as `[u64; 4]` additionally require
+/// `data` to be suitably aligned, returning [`ParseError::Misaligned`]
+/// otherwise.
+#[inline]
+pub fn split_keys<K: Key>(data: &[u8], count: usize) -> Result<(&[K], &[u8]), ParseError> {
+    let expected = count.checked_mul(32).ok_or(ParseError::TooShort {
+        expected: usize::MAX,
+        actual: data.len(),
+    })?;
+    if data.len() < expected {
+        return Err(ParseError::TooShort {
+            expected,
+            actual: data.len(),
+        });
+    }
+    if data.as_ptr().align_offset(core::mem::align_of::<K>()) != 0 {
+        return Err(ParseError::Misaligned);
+    }
+
+    let (keys, rest) = data.split_at(expected);
+    // SAFETY: `keys` is exactly `count * 32` bytes, suitably aligned for `K`
+    // (checked above), and `Key` guarantees `K` is 32 bytes with no padding
+    // and no invalid bit patterns.
+    let keys = unsafe { core::slice::from_raw_parts(keys.as_ptr() as *const K, count) };
+    Ok((keys, rest))
+}
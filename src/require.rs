@@ -0,0 +1,290 @@
+//! Validation helpers with a configurable failure strategy
+//!
+//! Every helper takes a [`FailureMode`] deciding what happens on mismatch:
+//! abort the transaction outright, return a custom error code, or log the
+//! failing check before returning the error. A [`Guard`] bundles a mode so a
+//! program can declare its policy once as a `const` and use it everywhere.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{Key, KeyOf, diag::log_failure, fast_eq, same_key};
+
+#[cfg(target_os = "solana")]
+unsafe extern "C" {
+    fn sol_panic_(file: *const u8, len: u64, line: u64, column: u64) -> !;
+}
+
+/// What a validation helper does when its check fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureMode {
+    /// Abort the transaction immediately via `sol_panic_`
+    ///
+    /// Cheapest in CU and code size; the runtime reports the caller's source
+    /// location rather than an error code.
+    Abort,
+    /// Return `ProgramError::Custom(code)`
+    Error(u32),
+    /// Log the name of the failing check, then return
+    /// `ProgramError::Custom(code)`
+    LogThenError(u32),
+}
+
+impl Default for FailureMode {
+    /// Returns [`FailureMode::Abort`], matching `assert!`-style semantics
+    fn default() -> Self {
+        FailureMode::Abort
+    }
+}
+
+impl FailureMode {
+    /// Applies this mode to a failed check named `check`
+    ///
+    /// Diverges for [`FailureMode::Abort`]; otherwise returns the error to
+    /// propagate.
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    pub fn fail(self, check: &str) -> ProgramError {
+        match self {
+            FailureMode::Abort => abort(check),
+            FailureMode::Error(code) => ProgramError::Custom(code),
+            FailureMode::LogThenError(code) => {
+                solana_program::log::sol_log(check);
+                ProgramError::Custom(code)
+            }
+        }
+    }
+}
+
+/// Aborts the program, reporting the caller's source location
+#[cold]
+#[track_caller]
+fn abort(check: &str) -> ! {
+    #[cfg(target_os = "solana")]
+    {
+        let _ = check;
+        let location = core::panic::Location::caller();
+        unsafe {
+            sol_panic_(
+                location.file().as_ptr(),
+                location.file().len() as u64,
+                location.line() as u64,
+                location.column() as u64,
+            )
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        panic!("{check} failed")
+    }
+}
+
+/// Requires two keys to be equal
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::{require_keys_eq, FailureMode};
+///
+/// let stored = [4u8; 32];
+/// let provided = [5u8; 32];
+/// assert!(require_keys_eq(&stored, &provided, FailureMode::Error(6000)).is_err());
+/// ```
+#[inline(always)]
+#[track_caller]
+pub fn require_keys_eq<K: Key>(lhs: &K, rhs: &K, mode: FailureMode) -> ProgramResult {
+    if fast_eq(lhs, rhs) {
+        Ok(())
+    } else {
+        Err(keys_eq_failed(lhs, rhs, mode))
+    }
+}
+
+/// Failure path of [`require_keys_eq`]
+#[cold]
+#[track_caller]
+pub(crate) fn keys_eq_failed<K: Key>(lhs: &K, rhs: &K, mode: FailureMode) -> ProgramError {
+    log_failure("require_keys_eq", None, &[lhs.key_bytes(), rhs.key_bytes()]);
+    mode.fail("require_keys_eq")
+}
+
+/// Requires two keys to differ
+#[inline(always)]
+#[track_caller]
+pub fn require_keys_neq<K: Key>(lhs: &K, rhs: &K, mode: FailureMode) -> ProgramResult {
+    if fast_eq(lhs, rhs) {
+        Err(keys_neq_failed(lhs, mode))
+    } else {
+        Ok(())
+    }
+}
+
+/// Failure path of [`require_keys_neq`]
+#[cold]
+#[track_caller]
+pub(crate) fn keys_neq_failed<K: Key>(key: &K, mode: FailureMode) -> ProgramError {
+    log_failure("require_keys_neq", None, &[key.key_bytes()]);
+    mode.fail("require_keys_neq")
+}
+
+/// Requires two accounts or keys to have the same key
+///
+/// [`require_keys_eq`] over [`KeyOf`], so an account can be checked against
+/// its expected key without spelling out `.key`:
+///
+/// ```rust,ignore
+/// require_same_key(vault_info, &expected_vault, FailureMode::Error(6001))?;
+/// ```
+#[inline(always)]
+#[track_caller]
+pub fn require_same_key<A: KeyOf, B: KeyOf>(lhs: &A, rhs: &B, mode: FailureMode) -> ProgramResult {
+    if same_key(lhs, rhs) {
+        Ok(())
+    } else {
+        Err(keys_eq_failed(
+            lhs.key_of().key_bytes(),
+            rhs.key_of().key_bytes(),
+            mode,
+        ))
+    }
+}
+
+/// Requires the first account to be `expected` and a signer
+///
+/// The fee payer is always the first account of the transaction, and
+/// programs that charge it conventionally take it first too.
+#[inline(always)]
+#[track_caller]
+pub fn require_fee_payer(
+    accounts: &[AccountInfo],
+    expected: &Pubkey,
+    mode: FailureMode,
+) -> ProgramResult {
+    require_signer_at(accounts, 0, expected, mode)
+}
+
+/// Requires the account at `index` to be `expected` and a signer
+///
+/// A missing account fails the same way as a mismatched or unsigned one.
+#[inline(always)]
+#[track_caller]
+pub fn require_signer_at(
+    accounts: &[AccountInfo],
+    index: usize,
+    expected: &Pubkey,
+    mode: FailureMode,
+) -> ProgramResult {
+    match accounts.get(index) {
+        Some(account) if account.is_signer && fast_eq(account.key, expected) => Ok(()),
+        account => Err(signer_at_failed(account, index, expected, mode)),
+    }
+}
+
+/// Failure path of [`require_signer_at`]
+#[cold]
+#[track_caller]
+fn signer_at_failed(
+    account: Option<&AccountInfo>,
+    index: usize,
+    expected: &Pubkey,
+    mode: FailureMode,
+) -> ProgramError {
+    match account {
+        Some(account) => log_failure(
+            "require_signer_at",
+            Some(index),
+            &[account.key.key_bytes(), expected.key_bytes()],
+        ),
+        None => log_failure("require_signer_at", Some(index), &[expected.key_bytes()]),
+    }
+    mode.fail("require_signer_at")
+}
+
+/// Asserts two keys are equal, aborting the program otherwise
+#[inline(always)]
+#[track_caller]
+pub fn assert_keys_eq<K: Key>(lhs: &K, rhs: &K) {
+    if !fast_eq(lhs, rhs) {
+        log_failure("assert_keys_eq", None, &[lhs.key_bytes(), rhs.key_bytes()]);
+        abort("assert_keys_eq")
+    }
+}
+
+/// A program-wide failure policy for the validation helpers
+///
+/// Declare one `const` guard and call the helpers through it, so every check
+/// in the program handles mismatches the same way:
+///
+/// ```rust
+/// use solana_pubkey_compare::{FailureMode, Guard};
+///
+/// const GUARD: Guard = Guard::new(FailureMode::LogThenError(6000));
+///
+/// let authority = [1u8; 32];
+/// assert!(GUARD.require_keys_eq(&authority, &authority).is_ok());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Guard {
+    /// Failure strategy applied by every helper called through this guard
+    pub mode: FailureMode,
+}
+
+impl Guard {
+    /// Creates a guard applying `mode` on every failed check
+    pub const fn new(mode: FailureMode) -> Self {
+        Guard { mode }
+    }
+
+    /// [`require_keys_eq`] with this guard's failure mode
+    #[inline(always)]
+    #[track_caller]
+    pub fn require_keys_eq<K: Key>(&self, lhs: &K, rhs: &K) -> ProgramResult {
+        require_keys_eq(lhs, rhs, self.mode)
+    }
+
+    /// [`require_keys_neq`] with this guard's failure mode
+    #[inline(always)]
+    #[track_caller]
+    pub fn require_keys_neq<K: Key>(&self, lhs: &K, rhs: &K) -> ProgramResult {
+        require_keys_neq(lhs, rhs, self.mode)
+    }
+
+    /// [`require_fee_payer`] with this guard's failure mode
+    #[inline(always)]
+    #[track_caller]
+    pub fn require_fee_payer(&self, accounts: &[AccountInfo], expected: &Pubkey) -> ProgramResult {
+        require_fee_payer(accounts, expected, self.mode)
+    }
+
+    /// [`require_signer_at`] with this guard's failure mode
+    #[inline(always)]
+    #[track_caller]
+    pub fn require_signer_at(
+        &self,
+        accounts: &[AccountInfo],
+        index: usize,
+        expected: &Pubkey,
+    ) -> ProgramResult {
+        require_signer_at(accounts, index, expected, self.mode)
+    }
+
+    /// [`init_or_require`](crate::init_or_require) with this guard's failure
+    /// mode
+    #[cfg(feature = "copy")]
+    #[inline(always)]
+    #[track_caller]
+    pub fn init_or_require<K: Key>(&self, slot: &mut [u8; 32], key: &K) -> ProgramResult {
+        crate::init_or_require(slot, key, self.mode)
+    }
+
+    /// [`require_same_key`] with this guard's failure mode
+    #[inline(always)]
+    #[track_caller]
+    pub fn require_same_key<A: KeyOf, B: KeyOf>(&self, lhs: &A, rhs: &B) -> ProgramResult {
+        require_same_key(lhs, rhs, self.mode)
+    }
+}
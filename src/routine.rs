@@ -0,0 +1,17 @@
+//! Stable numeric identifiers for the crate's routines
+//!
+//! Used wherever a routine has to be named in account data, such as the
+//! shadow divergence log and the telemetry counters. Values are never
+//! reused, so records written by older builds stay meaningful.
+
+/// `fast_eq`
+pub const FAST_EQ: u8 = 0;
+/// `fast_eq_branchless`
+pub const FAST_EQ_BRANCHLESS: u8 = 1;
+/// `require_keys_eq`
+pub const REQUIRE_KEYS_EQ: u8 = 2;
+/// `require_keys_neq`
+pub const REQUIRE_KEYS_NEQ: u8 = 3;
+
+/// Number of routine identifiers assigned so far
+pub const COUNT: usize = 4;
@@ -0,0 +1,593 @@
+//! Key scans over raw account data
+//!
+//! Order books, registries and escrow lists typically store fixed-size
+//! records back to back, each with a 32-byte key field at a fixed position.
+//! These helpers compare that field of every record against a target without
+//! deserializing the records.
+
+use crate::{Key, fast_eq};
+
+/// Returns the key stored at `offset` in `data`
+///
+/// # Safety
+///
+/// `offset + 32` must not exceed `data.len()`.
+#[inline(always)]
+pub(crate) unsafe fn key_at(data: &[u8], offset: usize) -> &[u8; 32] {
+    unsafe { &*(data.as_ptr().add(offset) as *const [u8; 32]) }
+}
+
+/// Finds the first fixed-size record whose key field equals `key`
+///
+/// Record `i` has its key at `start_offset + i * stride`. Records are scanned
+/// in order until one matches or the next key field would run past the end
+/// of `data`. Returns the record index `i`, not a byte offset.
+///
+/// On Solana the whole scan is one assembly loop that keeps the key's first
+/// 8 bytes in a register: a record whose key differs there costs 4
+/// instructions including loop control. Natively the loop is unrolled four
+/// records at a time.
+///
+/// # Panics
+///
+/// Panics if `stride` is zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::scan_keys;
+///
+/// // 8-byte header, then 40-byte records: 32-byte owner + 8-byte amount
+/// let mut data = vec![0u8; 8 + 40 * 3];
+/// data[8 + 40 * 2..8 + 40 * 2 + 32].copy_from_slice(&[9u8; 32]);
+///
+/// assert_eq!(scan_keys(&data, 8, 40, &[9u8; 32]), Some(2));
+/// assert_eq!(scan_keys(&data, 8, 40, &[1u8; 32]), None);
+/// ```
+#[inline]
+pub fn scan_keys<K: Key>(
+    data: &[u8],
+    start_offset: usize,
+    stride: usize,
+    key: &K,
+) -> Option<usize> {
+    assert!(stride != 0, "scan_keys: stride must be non-zero");
+    let key = key.key_bytes();
+    let count = match data
+        .len()
+        .checked_sub(32)
+        .and_then(|last| last.checked_sub(start_offset))
+    {
+        Some(span) => span / stride + 1,
+        None => return None,
+    };
+
+    #[cfg(target_os = "solana")]
+    {
+        // SAFETY: record `count - 1` ends at or before `data.len()`, and the
+        // routine reads no record at or past `count`. It returns `count`
+        // when nothing matches.
+        let index = unsafe {
+            crate::__solana_pubkey_compare__scan_strided(
+                data.as_ptr().add(start_offset),
+                count as u64,
+                stride as u64,
+                key.as_ptr(),
+            )
+        } as usize;
+        (index < count).then_some(index)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        scan_keys_unrolled(data, start_offset, stride, key, count)
+    }
+}
+
+/// Native loop of [`scan_keys`] over `count` records
+#[cfg(not(target_os = "solana"))]
+#[inline(always)]
+fn scan_keys_unrolled(
+    data: &[u8],
+    start_offset: usize,
+    stride: usize,
+    key: &[u8; 32],
+    count: usize,
+) -> Option<usize> {
+    let mut i = 0;
+    let mut offset = start_offset;
+    // SAFETY (all `key_at` calls): record `count - 1` ends at or before
+    // `data.len()`, and only indices below `count` are read.
+    while i + 4 <= count {
+        unsafe {
+            if fast_eq(key_at(data, offset), key) {
+                return Some(i);
+            }
+            if fast_eq(key_at(data, offset + stride), key) {
+                return Some(i + 1);
+            }
+            if fast_eq(key_at(data, offset + 2 * stride), key) {
+                return Some(i + 2);
+            }
+            if fast_eq(key_at(data, offset + 3 * stride), key) {
+                return Some(i + 3);
+            }
+        }
+        i += 4;
+        offset += 4 * stride;
+    }
+    while i < count {
+        if fast_eq(unsafe { key_at(data, offset) }, key) {
+            return Some(i);
+        }
+        i += 1;
+        offset += stride;
+    }
+    None
+}
+
+/// Returns `true` if `needle` occurs in `haystack`
+///
+/// The whole scan runs in one assembly loop instead of a [`fast_eq`] call
+/// per element. The needle's first 8 bytes are held in a register, so each
+/// element that differs there costs 4 instructions including loop control.
+/// Suited to allowlisting remaining accounts against a short list.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::contains;
+///
+/// let allowed = [[1u8; 32], [2u8; 32], [3u8; 32]];
+/// assert!(contains(&allowed, &[2u8; 32]));
+/// assert!(!contains(&allowed, &[4u8; 32]));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_contains.s`.
+#[inline(always)]
+pub fn contains<K: Key>(haystack: &[K], needle: &K) -> bool {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        // `Key` types are exactly 32 bytes, so elements are 32 bytes apart.
+        crate::__solana_pubkey_compare__contains(
+            haystack.as_ptr() as *const u8,
+            haystack.len() as u64,
+            needle.key_ptr(),
+        )
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        haystack
+            .iter()
+            .any(|key| key.key_bytes() == needle.key_bytes())
+    }
+}
+
+/// Returns `true` if any key yielded by `keys` equals `target`
+///
+/// The iterator counterpart of [`contains`], for collections that are not a
+/// contiguous slice: zero-copy lists, chained account iterators, or keys
+/// borrowed from several places. Each key is compared with [`fast_eq`] and
+/// the iteration stops at the first match.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::any_eq;
+///
+/// let (signers, delegates) = ([[1u8; 32]], [[2u8; 32], [3u8; 32]]);
+/// assert!(any_eq(signers.iter().chain(&delegates), &[3u8; 32]));
+/// ```
+#[inline]
+pub fn any_eq<'a, K: Key + 'a>(keys: impl IntoIterator<Item = &'a K>, target: &K) -> bool {
+    keys.into_iter().any(|key| fast_eq(key, target))
+}
+
+/// Returns the index of the first key in `keys` equal to `target`
+///
+/// The [`contains`] loop, reporting where the key was found, for pairing
+/// remaining accounts with configuration entries. The index is computed
+/// from the element pointer after the loop, so the per-element cost is the
+/// same as [`contains`].
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::position_of;
+///
+/// let keys = [[1u8; 32], [2u8; 32], [2u8; 32]];
+/// assert_eq!(position_of(&keys, &[2u8; 32]), Some(1));
+/// assert_eq!(position_of(&keys, &[4u8; 32]), None);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_position.s`.
+#[inline(always)]
+pub fn position_of<K: Key>(keys: &[K], target: &K) -> Option<usize> {
+    #[cfg(target_os = "solana")]
+    {
+        // SAFETY: `Key` types are exactly 32 bytes, so elements are 32
+        // bytes apart. The routine returns `keys.len()` when nothing matches.
+        let index = unsafe {
+            crate::__solana_pubkey_compare__position_of(
+                keys.as_ptr() as *const u8,
+                keys.len() as u64,
+                target.key_ptr(),
+            )
+        } as usize;
+        (index < keys.len()).then_some(index)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        keys.iter()
+            .position(|key| key.key_bytes() == target.key_bytes())
+    }
+}
+
+/// Returns the number of keys in `keys` equal to `target`
+///
+/// The [`contains`] loop without the early exit, for tallying how many
+/// provided signers match a multisig member and similar counts.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::count_eq;
+///
+/// let signers = [[1u8; 32], [2u8; 32], [1u8; 32]];
+/// assert_eq!(count_eq(&signers, &[1u8; 32]), 2);
+/// assert_eq!(count_eq(&signers, &[3u8; 32]), 0);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_count.s`.
+#[inline(always)]
+pub fn count_eq<K: Key>(keys: &[K], target: &K) -> usize {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        // `Key` types are exactly 32 bytes, so elements are 32 bytes apart.
+        crate::__solana_pubkey_compare__count_eq(
+            keys.as_ptr() as *const u8,
+            keys.len() as u64,
+            target.key_ptr(),
+        ) as usize
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        keys.iter()
+            .filter(|key| key.key_bytes() == target.key_bytes())
+            .count()
+    }
+}
+
+/// Returns the number of `members` that equal one of the provided `signers`
+///
+/// The threshold check of a multisig in one call: compare the result with
+/// the required number of signatures. Each member is counted at most once,
+/// so passing the same signer twice does not count towards the threshold
+/// twice. Both loops run in one assembly routine; a signer that differs
+/// from the member in its first 8 bytes costs 5 instructions.
+///
+/// Members are expected to be distinct, as multisig configurations enforce
+/// on creation; a repeated member is counted each time it appears.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::count_members;
+///
+/// let members = [[1u8; 32], [2u8; 32], [3u8; 32]];
+/// let (a, c, x) = ([1u8; 32], [3u8; 32], [9u8; 32]);
+/// assert_eq!(count_members(&[&a, &x, &c], &members), 2);
+/// assert_eq!(count_members(&[&a, &a], &members), 1);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_count_members.s`.
+#[inline(always)]
+pub fn count_members<K: Key>(signers: &[&K], members: &[K]) -> usize {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        // `Key` types are exactly 32 bytes and `&K` is a thin pointer.
+        crate::__solana_pubkey_compare__count_members(
+            signers.as_ptr() as *const *const u8,
+            signers.len() as u64,
+            members.as_ptr() as *const u8,
+            members.len() as u64,
+        ) as usize
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        members
+            .iter()
+            .filter(|member| {
+                signers
+                    .iter()
+                    .any(|signer| signer.key_bytes() == member.key_bytes())
+            })
+            .count()
+    }
+}
+
+/// Returns `true` if `lhs` and `rhs` hold the same keys in the same order
+///
+/// After the length check, every pair is compared in one assembly loop with
+/// the early-exit chain of [`fast_eq`], for verifying provided accounts
+/// against a stored ordered list. Each equal pair costs 15 instructions.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::slices_eq;
+///
+/// let stored = [[1u8; 32], [2u8; 32]];
+/// assert!(slices_eq(&stored, &[[1u8; 32], [2u8; 32]]));
+/// assert!(!slices_eq(&stored, &[[2u8; 32], [1u8; 32]]));
+/// assert!(!slices_eq(&stored, &[[1u8; 32]]));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_slices_eq.s`.
+#[inline(always)]
+pub fn slices_eq<K: Key>(lhs: &[K], rhs: &[K]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+
+    #[cfg(target_os = "solana")]
+    unsafe {
+        // `Key` types are exactly 32 bytes, so elements are 32 bytes apart.
+        crate::__solana_pubkey_compare__slices_eq(
+            lhs.as_ptr() as *const u8,
+            lhs.len() as u64,
+            rhs.as_ptr() as *const u8,
+        )
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        lhs.iter()
+            .zip(rhs)
+            .all(|(lhs, rhs)| lhs.key_bytes() == rhs.key_bytes())
+    }
+}
+
+/// Checks provided keys against expected keys pairwise, returning the first
+/// mismatching index
+///
+/// The account-validation prologue in one call: `expected[i]` is compared
+/// with `*actual[i]` for each `i` in a single assembly loop, at 16
+/// instructions per matching pair. If the lengths differ, the first index
+/// past the shorter slice is reported as the mismatch.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::validate_keys;
+///
+/// let expected = [[1u8; 32], [2u8; 32], [3u8; 32]];
+/// let (a, b, c) = ([1u8; 32], [2u8; 32], [9u8; 32]);
+/// assert_eq!(validate_keys(&expected, &[&a, &b, &c]), Err(2));
+/// assert_eq!(validate_keys(&expected[..2], &[&a, &b]), Ok(()));
+/// assert_eq!(validate_keys(&expected[..2], &[&a]), Err(1));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_validate.s`.
+#[inline]
+pub fn validate_keys<K: Key>(expected: &[K], actual: &[&K]) -> Result<(), usize> {
+    let len = expected.len().min(actual.len());
+
+    #[cfg(target_os = "solana")]
+    // SAFETY: both slices hold at least `len` entries, `Key` types are
+    // exactly 32 bytes and `&K` is a thin pointer.
+    let index = unsafe {
+        crate::__solana_pubkey_compare__validate_keys(
+            expected.as_ptr() as *const u8,
+            len as u64,
+            actual.as_ptr() as *const *const u8,
+        )
+    } as usize;
+
+    #[cfg(not(target_os = "solana"))]
+    let index = expected
+        .iter()
+        .zip(actual)
+        .position(|(expected, actual)| expected.key_bytes() != actual.key_bytes())
+        .unwrap_or(len);
+
+    if index < len || expected.len() != actual.len() {
+        Err(index)
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns `true` if every key in `keys` equals the first
+///
+/// For requiring a batch of accounts to share one authority or mint. The
+/// check runs in one assembly loop against element 0, whose first 8 bytes
+/// are held in a register; each equal element costs 13 instructions. Empty
+/// and single-key slices are trivially all equal.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::all_equal;
+///
+/// assert!(all_equal(&[[6u8; 32], [6u8; 32], [6u8; 32]]));
+/// assert!(!all_equal(&[[6u8; 32], [6u8; 32], [7u8; 32]]));
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_all_equal.s`.
+#[inline(always)]
+pub fn all_equal<K: Key>(keys: &[K]) -> bool {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        // `Key` types are exactly 32 bytes, so elements are 32 bytes apart.
+        crate::__solana_pubkey_compare__all_equal(keys.as_ptr() as *const u8, keys.len() as u64)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        keys.windows(2)
+            .all(|pair| pair[0].key_bytes() == pair[1].key_bytes())
+    }
+}
+
+/// Finds the first byte offset at which `needle` occurs in `data`
+///
+/// Unlike [`scan_keys`], the key may start at any byte offset, so this works
+/// on opaque account layouts without a schema. Each candidate offset is first
+/// filtered on the needle's leading 8 bytes with a single unaligned load; the
+/// full 32-byte comparison only runs on prefix hits.
+///
+/// On Solana this is the [`scan_keys`] assembly loop with a stride of one
+/// byte, costing 4 instructions per candidate offset.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::find_key_in_bytes;
+///
+/// let mut data = [0u8; 100];
+/// data[13..45].copy_from_slice(&[5u8; 32]);
+///
+/// assert_eq!(find_key_in_bytes(&data, &[5u8; 32]), Some(13));
+/// ```
+#[inline]
+pub fn find_key_in_bytes<K: Key>(data: &[u8], needle: &K) -> Option<usize> {
+    let needle = needle.key_bytes();
+    let last = data.len().checked_sub(32)?;
+
+    #[cfg(target_os = "solana")]
+    {
+        // SAFETY: the candidate at offset `last` ends at `data.len()`, and the
+        // routine reads none past it. It returns `last + 1` when nothing
+        // matches.
+        let offset = unsafe {
+            crate::__solana_pubkey_compare__scan_strided(
+                data.as_ptr(),
+                last as u64 + 1,
+                1,
+                needle.as_ptr(),
+            )
+        } as usize;
+        (offset <= last).then_some(offset)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        find_key_in_bytes_native(data, needle, last)
+    }
+}
+
+/// Native loop of [`find_key_in_bytes`] over offsets `0..=last`
+#[cfg(not(target_os = "solana"))]
+#[inline(always)]
+fn find_key_in_bytes_native(data: &[u8], needle: &[u8; 32], last: usize) -> Option<usize> {
+    let prefix = u64::from_ne_bytes([
+        needle[0], needle[1], needle[2], needle[3], needle[4], needle[5], needle[6], needle[7],
+    ]);
+
+    let base = data.as_ptr();
+    for offset in 0..=last {
+        // SAFETY: `offset + 32 <= data.len()`, so the 8-byte prefix read and
+        // the 32-byte verify both stay in bounds.
+        unsafe {
+            if core::ptr::read_unaligned(base.add(offset) as *const u64) == prefix
+                && key_at(data, offset) == needle
+            {
+                return Some(offset);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the first all-zero 32-byte entry in a packed key array
+///
+/// Registry accounts mark free slots with `Pubkey::default()`; this returns
+/// the index of the first such entry, for claiming a slot on insert. Entry
+/// `i` occupies bytes `32 * i..32 * (i + 1)` of `data`, and trailing bytes
+/// that do not fill a whole entry are ignored.
+///
+/// On Solana the [`fast_is_zero`](crate::fast_is_zero) chunk test runs over
+/// the whole array in one assembly loop, at 4 instructions per occupied
+/// entry with a set byte in its first 8 bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::find_zero_slot;
+///
+/// let mut registry = [7u8; 32 * 4];
+/// registry[64..96].fill(0);
+///
+/// assert_eq!(find_zero_slot(&registry), Some(2));
+/// assert_eq!(find_zero_slot(&registry[..64]), None);
+/// ```
+///
+/// # Implementation Notes
+///
+/// See `src/asm/scan_pubkey_zero_slot.s`.
+#[inline(always)]
+pub fn find_zero_slot(data: &[u8]) -> Option<usize> {
+    let count = data.len() / 32;
+
+    #[cfg(target_os = "solana")]
+    {
+        // SAFETY: entry `count - 1` ends at or before `data.len()`. The
+        // routine returns `count` when no entry is zero.
+        let index =
+            unsafe { crate::__solana_pubkey_compare__find_zero_slot(data.as_ptr(), count as u64) }
+                as usize;
+        (index < count).then_some(index)
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        data[..count * 32]
+            .chunks_exact(32)
+            .position(|entry| entry.iter().all(|&byte| byte == 0))
+    }
+}
+
+/// Compares the 32 bytes at `offset` in `data` against `key`
+///
+/// This is the building block for checking key fields inside raw account
+/// data in place, without copying them out. Returns `false` if the field
+/// would run past the end of `data`.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::fast_eq_at;
+///
+/// let mut data = [0u8; 72];
+/// data[40..72].copy_from_slice(&[2u8; 32]);
+///
+/// assert!(fast_eq_at(&data, 40, &[2u8; 32]));
+/// assert!(!fast_eq_at(&data, 41, &[2u8; 32]));
+/// ```
+#[inline(always)]
+pub fn fast_eq_at<K: Key>(data: &[u8], offset: usize, key: &K) -> bool {
+    match offset.checked_add(32) {
+        // SAFETY: the field ends within `data`.
+        Some(end) if end <= data.len() => fast_eq(unsafe { key_at(data, offset) }, key.key_bytes()),
+        _ => false,
+    }
+}
@@ -0,0 +1,69 @@
+//! PDA seed comparison
+//!
+//! Programs that store a PDA's canonical seeds in account data must check
+//! caller-supplied seeds against them before `invoke_signed`. Seeds are
+//! short (at most 32 bytes each), so they are compared a word at a time
+//! rather than through `memcmp`.
+
+use crate::eq_raw;
+
+/// Returns `true` if two seed arrays hold the same seeds in the same order
+///
+/// Seed counts and each seed's length are compared before any bytes, so a
+/// mismatch in shape exits without reading seed data. 32-byte seeds, usually
+/// keys, go through the assembly comparator.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::seeds_eq;
+///
+/// let owner = [4u8; 32];
+/// let stored: &[&[u8]] = &[b"vault", &owner, &[254]];
+///
+/// assert!(seeds_eq(stored, &[b"vault", &owner, &[254]]));
+/// assert!(!seeds_eq(stored, &[b"vault", &owner, &[253]]));
+/// assert!(!seeds_eq(stored, &[b"vault", &owner]));
+/// ```
+#[inline]
+pub fn seeds_eq(lhs: &[&[u8]], rhs: &[&[u8]]) -> bool {
+    lhs.len() == rhs.len()
+        && lhs
+            .iter()
+            .zip(rhs)
+            .all(|(lhs, rhs)| lhs.len() == rhs.len() && memeq(lhs, rhs))
+}
+
+/// Compares two equal-length byte slices
+///
+/// Whole 8-byte words are compared with unaligned loads, then the remaining
+/// bytes one by one.
+#[inline(always)]
+fn memeq(lhs: &[u8], rhs: &[u8]) -> bool {
+    debug_assert_eq!(lhs.len(), rhs.len());
+    let len = lhs.len();
+    let (lhs, rhs) = (lhs.as_ptr(), rhs.as_ptr());
+    // SAFETY: both slices are `len` bytes long, and every read below ends at
+    // or before `len`.
+    unsafe {
+        if len == 32 {
+            return eq_raw(lhs, rhs);
+        }
+        let mut at = 0;
+        while at + 8 <= len {
+            if core::ptr::read_unaligned(lhs.add(at) as *const u64)
+                != core::ptr::read_unaligned(rhs.add(at) as *const u64)
+            {
+                return false;
+            }
+            at += 8;
+        }
+        while at < len {
+            if *lhs.add(at) != *rhs.add(at) {
+                return false;
+            }
+            at += 1;
+        }
+    }
+    true
+}
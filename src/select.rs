@@ -0,0 +1,169 @@
+//! Selection and partitioning over key slices
+
+use core::cmp::Ordering;
+
+use crate::{Key, fast_eq, sorted::order};
+
+/// Returns the smallest key in `keys`, in byte order
+///
+/// One pass with the `fast_cmp` comparator. Picks the canonical member of a
+/// key set, such as the smaller mint when deriving a pool address.
+///
+/// # Panics
+///
+/// Panics if `keys` is empty.
+///
+/// ```rust
+/// use solana_pubkey_compare::min_key;
+///
+/// assert_eq!(min_key(&[[3u8; 32], [1u8; 32], [2u8; 32]]), &[1u8; 32]);
+/// ```
+#[inline]
+pub fn min_key<K: Key>(keys: &[K]) -> &K {
+    keys.iter()
+        .min_by(|lhs, rhs| order(*lhs, *rhs))
+        .expect("min_key: keys must not be empty")
+}
+
+/// Returns the largest key in `keys`, in byte order
+///
+/// The counterpart of [`min_key`].
+///
+/// # Panics
+///
+/// Panics if `keys` is empty.
+#[inline]
+pub fn max_key<K: Key>(keys: &[K]) -> &K {
+    keys.iter()
+        .max_by(|lhs, rhs| order(*lhs, *rhs))
+        .expect("max_key: keys must not be empty")
+}
+
+/// Reorders `keys` so the `k`-th smallest key (from zero, in byte order) is
+/// at index `k`, and returns it
+///
+/// Keys before index `k` are no greater and keys after it no smaller, in no
+/// particular order. Runs in expected `O(n)` via introselect, so medians and
+/// percentiles of addresses need no full sort.
+///
+/// # Panics
+///
+/// Panics if `k >= keys.len()`.
+///
+/// ```rust
+/// use solana_pubkey_compare::select_kth;
+///
+/// let mut keys = [[3u8; 32], [1u8; 32], [2u8; 32]];
+/// assert_eq!(select_kth(&mut keys, 1), &[2u8; 32]);
+/// ```
+#[inline]
+pub fn select_kth<K: Key>(keys: &mut [K], k: usize) -> &K {
+    keys.select_nth_unstable_by(k, order).1
+}
+
+/// Writes the `k` smallest keys of `keys` into `out` in ascending byte order,
+/// returning how many were written
+///
+/// `out[..n]` holds the result, where `n` is `k` or `keys.len()` if that is
+/// smaller; repeated keys are kept. The keys pass through a bounded max-heap
+/// of `k` entries kept in `out`, so picking a canonical subset of a long
+/// validator list costs `O(n log k)` comparisons with the `fast_cmp`
+/// comparator instead of a full sort, and `keys` is left untouched.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than `n`.
+///
+/// ```rust
+/// use solana_pubkey_compare::select_k_smallest;
+///
+/// let validators = [[5u8; 32], [1u8; 32], [4u8; 32], [2u8; 32]];
+/// let mut canonical = [[0u8; 32]; 2];
+/// assert_eq!(select_k_smallest(&validators, 2, &mut canonical), 2);
+/// assert_eq!(canonical, [[1u8; 32], [2u8; 32]]);
+/// ```
+#[inline]
+pub fn select_k_smallest<K: Key + Copy>(keys: &[K], k: usize, out: &mut [K]) -> usize {
+    let n = k.min(keys.len());
+    let heap = &mut out[..n];
+    if n == 0 {
+        return 0;
+    }
+
+    for (i, key) in keys[..n].iter().enumerate() {
+        heap[i] = *key;
+        sift_up(heap, i);
+    }
+    for key in &keys[n..] {
+        if order(key, &heap[0]) == Ordering::Less {
+            heap[0] = *key;
+            sift_down(heap, 0, n);
+        }
+    }
+
+    // Heapsort in place: move the largest remaining key to the back
+    for end in (1..n).rev() {
+        heap.swap(0, end);
+        sift_down(heap, 0, end);
+    }
+    n
+}
+
+/// Restores the max-heap order of `heap` after `heap[i]` was appended
+#[inline(always)]
+fn sift_up<K: Key>(heap: &mut [K], mut i: usize) {
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if order(&heap[parent], &heap[i]) != Ordering::Less {
+            break;
+        }
+        heap.swap(parent, i);
+        i = parent;
+    }
+}
+
+/// Restores the max-heap order of `heap[..len]` after `heap[i]` was lowered
+#[inline(always)]
+fn sift_down<K: Key>(heap: &mut [K], mut i: usize, len: usize) {
+    loop {
+        let mut largest = i;
+        for child in [2 * i + 1, 2 * i + 2] {
+            if child < len && order(&heap[child], &heap[largest]) == Ordering::Greater {
+                largest = child;
+            }
+        }
+        if largest == i {
+            break;
+        }
+        heap.swap(i, largest);
+        i = largest;
+    }
+}
+
+/// Moves every key equal to `key` to the front of `keys`, returning how many
+/// there are
+///
+/// Keys equal to `key` end up in `keys[..n]` and all others in `keys[n..]`.
+/// The relative order of the other keys is not preserved. Each key is
+/// compared once with [`fast_eq`] and moved at most once.
+///
+/// ```rust
+/// use solana_pubkey_compare::partition_eq;
+///
+/// let mine = [7u8; 32];
+/// let mut keys = [[1u8; 32], mine, [2u8; 32], mine];
+/// let n = partition_eq(&mut keys, &mine);
+/// assert_eq!(n, 2);
+/// assert_eq!(keys[..n], [mine, mine]);
+/// ```
+#[inline]
+pub fn partition_eq<K: Key>(keys: &mut [K], key: &K) -> usize {
+    let mut split = 0;
+    for i in 0..keys.len() {
+        if fast_eq(&keys[i], key) {
+            keys.swap(split, i);
+            split += 1;
+        }
+    }
+    split
+}
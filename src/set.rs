@@ -0,0 +1,452 @@
+//! Set operations over key slices
+//!
+//! Key lists are treated as sets: order and repeated entries do not matter.
+//! Routines that need ordering compare keys lexicographically by their bytes,
+//! matching `Pubkey`'s `Ord`.
+
+use core::cmp::Ordering;
+
+use crate::{Key, fast_eq, sorted::order};
+
+/// Lexicographic byte order of two keys, matching `Pubkey`'s `Ord`
+///
+/// Compares big-endian words, which order like their bytes, so the common
+/// case resolves on the first word without a `memcmp` call.
+#[inline(always)]
+pub(crate) fn key_cmp<K: Key>(lhs: &K, rhs: &K) -> Ordering {
+    let (lhs, rhs) = (lhs.key_bytes(), rhs.key_bytes());
+    for at in [0, 8, 16, 24] {
+        let ordering = be_word(lhs, at).cmp(&be_word(rhs, at));
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[inline(always)]
+fn be_word(key: &[u8; 32], at: usize) -> u64 {
+    u64::from_be_bytes(key[at..at + 8].try_into().unwrap())
+}
+
+/// Returns `true` if `needle` occurs anywhere in `haystack`
+#[inline(always)]
+fn contains_key<K: Key>(haystack: &[K], needle: &K) -> bool {
+    haystack.iter().any(|key| fast_eq(key, needle))
+}
+
+/// Returns `true` if two unordered key lists contain exactly the same keys
+///
+/// Every key of `a` must appear in `b` and vice versa; order and repeated
+/// entries are ignored. This is the allocation-free quadratic path, which is
+/// the cheapest option for the small lists (a few dozen keys) typical of
+/// council or signer sets. For larger lists that may be reordered, use
+/// [`set_eq_in_place`].
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::set_eq;
+///
+/// let snapshot = [[1u8; 32], [2u8; 32], [3u8; 32]];
+/// let current = [[3u8; 32], [1u8; 32], [2u8; 32]];
+/// assert!(set_eq(&snapshot, &current));
+/// ```
+#[inline]
+pub fn set_eq<K: Key>(a: &[K], b: &[K]) -> bool {
+    is_subset_of_unsorted(a, b) && is_subset_of_unsorted(b, a)
+}
+
+/// Like [`set_eq`], but sorts both lists in place first
+///
+/// Runs in `O(n log n)` instead of `O(n * m)`. Both slices are left sorted
+/// in byte order, which callers can reuse for later binary searches.
+#[inline]
+pub fn set_eq_in_place<K: Key>(a: &mut [K], b: &mut [K]) -> bool {
+    a.sort_unstable_by(key_cmp);
+    b.sort_unstable_by(key_cmp);
+
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if !fast_eq(&a[i], &b[j]) {
+            return false;
+        }
+        // Skip over repeats of the key just matched on both sides
+        i = skip_run(a, i);
+        j = skip_run(b, j);
+    }
+    i == a.len() && j == b.len()
+}
+
+/// Returns `true` if no key occurs twice in `keys`
+///
+/// Rejects instruction account lists that name the same account twice,
+/// which would otherwise let one account play two roles. Each key is
+/// searched for among the keys after it with the [`contains`] assembly
+/// loop, so `n` keys take `n * (n - 1) / 2` element checks at 4 instructions
+/// each when keys differ in their first 8 bytes. This is the cheapest option
+/// for the short lists typical of instruction accounts; for longer lists
+/// that may be reordered, use [`all_unique_in_place`].
+///
+/// [`contains`]: crate::contains
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::all_unique;
+///
+/// assert!(all_unique(&[[1u8; 32], [2u8; 32], [3u8; 32]]));
+/// assert!(!all_unique(&[[1u8; 32], [2u8; 32], [1u8; 32]]));
+/// ```
+#[inline]
+pub fn all_unique<K: Key>(keys: &[K]) -> bool {
+    (1..keys.len()).all(|i| !crate::contains(&keys[i..], &keys[i - 1]))
+}
+
+/// Like [`all_unique`], but sorts `keys` in place first
+///
+/// Runs in `O(n log n)` instead of `O(n^2)`: after [`sort_keys`], any
+/// repeat sits next to its first occurrence. The slice is left sorted in
+/// byte order.
+///
+/// [`sort_keys`]: crate::sort_keys
+#[inline]
+pub fn all_unique_in_place<K: Key>(keys: &mut [K]) -> bool {
+    crate::sort_keys(keys);
+    keys.windows(2).all(|pair| !fast_eq(&pair[0], &pair[1]))
+}
+
+/// Sorts `keys` and moves one copy of each distinct key to the front,
+/// returning how many there are
+///
+/// Builds a canonical set on-chain, for example from signer lists: after the
+/// call `keys[..n]` is sorted in byte order with no repeats, and the removed
+/// duplicates are left in `keys[n..]` in unspecified order.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::dedup_in_place;
+///
+/// let mut signers = [[3u8; 32], [1u8; 32], [3u8; 32]];
+/// let n = dedup_in_place(&mut signers);
+/// assert_eq!(signers[..n], [[1u8; 32], [3u8; 32]]);
+/// ```
+#[inline]
+pub fn dedup_in_place<K: Key>(keys: &mut [K]) -> usize {
+    crate::sort_keys(keys);
+    let mut len = keys.len().min(1);
+    for i in 1..keys.len() {
+        if !fast_eq(&keys[i], &keys[len - 1]) {
+            keys.swap(len, i);
+            len += 1;
+        }
+    }
+    len
+}
+
+/// Returns `true` if every key of sorted `a` also occurs in sorted `b`
+///
+/// Both slices must be sorted in byte order (as produced by
+/// [`set_eq_in_place`] or any `Pubkey` sort); repeated entries are allowed.
+/// The check is a single merge walk over both slices, `O(a.len() + b.len())`.
+/// On unsorted input the result is unspecified but memory-safe; use
+/// [`is_subset_of_unsorted`] for small unsorted lists.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::is_subset_of;
+///
+/// let owners = [[1u8; 32], [4u8; 32], [7u8; 32]];
+/// let signers = [[1u8; 32], [7u8; 32]];
+/// assert!(is_subset_of(&signers, &owners));
+/// ```
+#[inline]
+pub fn is_subset_of<K: Key>(a: &[K], b: &[K]) -> bool {
+    let mut j = 0;
+    for key in a {
+        while j < b.len() && key_cmp(&b[j], key) == Ordering::Less {
+            j += 1;
+        }
+        if j == b.len() || !fast_eq(&b[j], key) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Returns `true` if every key of sorted `b` also occurs in sorted `a`
+///
+/// Equivalent to `is_subset_of(b, a)`; see [`is_subset_of`] for the input
+/// requirements.
+#[inline]
+pub fn is_superset_of<K: Key>(a: &[K], b: &[K]) -> bool {
+    is_subset_of(b, a)
+}
+
+/// Returns `true` if every key of `a` also occurs in `b`, in any order
+///
+/// The quadratic counterpart of [`is_subset_of`] for small unsorted lists.
+#[inline]
+pub fn is_subset_of_unsorted<K: Key>(a: &[K], b: &[K]) -> bool {
+    a.iter().all(|key| contains_key(b, key))
+}
+
+/// Writes the keys present in exactly one of two sorted lists into `out`
+///
+/// Both inputs must be sorted in byte order. The output is sorted and each
+/// distinct key is written once, even if it is repeated in its input. Returns
+/// the number of keys written; `out[..n]` holds the result.
+///
+/// Typical use is reconciliation: the difference between an old and a new
+/// membership list is exactly the set of changes to apply.
+///
+/// # Panics
+///
+/// Panics if `out` is too short. `a.len() + b.len()` keys always suffice.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::symmetric_difference;
+///
+/// let old = [[1u8; 32], [2u8; 32], [3u8; 32]];
+/// let new = [[2u8; 32], [3u8; 32], [4u8; 32]];
+/// let mut changes = [[0u8; 32]; 6];
+///
+/// let n = symmetric_difference(&old, &new, &mut changes);
+/// assert_eq!(&changes[..n], &[[1u8; 32], [4u8; 32]]);
+/// ```
+#[inline]
+pub fn symmetric_difference<K: Key + Copy>(a: &[K], b: &[K], out: &mut [K]) -> usize {
+    let (mut i, mut j, mut n) = (0, 0, 0);
+    while i < a.len() || j < b.len() {
+        let order = match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) => key_cmp(x, y),
+            (Some(_), None) => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+        match order {
+            Ordering::Less => {
+                out[n] = a[i];
+                n += 1;
+                i = skip_run(a, i);
+            }
+            Ordering::Greater => {
+                out[n] = b[j];
+                n += 1;
+                j = skip_run(b, j);
+            }
+            Ordering::Equal => {
+                i = skip_run(a, i);
+                j = skip_run(b, j);
+            }
+        }
+    }
+    n
+}
+
+/// Writes the keys present in both of two sorted lists into `out`
+///
+/// Both inputs must be sorted in byte order. The output is sorted and each
+/// common key is written once, even if it is repeated in either input.
+/// Returns the number of keys written; `out[..n]` holds the result. Nothing
+/// is allocated, so an on-chain allowlist can be intersected with the
+/// provided signers directly.
+///
+/// The merge walk makes one [`fast_cmp`](crate::fast_cmp) call per step
+/// when the `cmp` feature is enabled, so each pair of keys is ordered and
+/// tested for equality at once.
+///
+/// # Panics
+///
+/// Panics if `out` is too short. `a.len().min(b.len())` keys always suffice.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::intersect_sorted;
+///
+/// let allowlist = [[1u8; 32], [3u8; 32], [5u8; 32]];
+/// let signers = [[2u8; 32], [3u8; 32], [5u8; 32]];
+/// let mut approved = [[0u8; 32]; 3];
+///
+/// let n = intersect_sorted(&allowlist, &signers, &mut approved);
+/// assert_eq!(&approved[..n], &[[3u8; 32], [5u8; 32]]);
+/// ```
+#[inline]
+pub fn intersect_sorted<K: Key + Copy>(a: &[K], b: &[K], out: &mut [K]) -> usize {
+    let (mut i, mut j, mut n) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match order(&a[i], &b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                out[n] = a[i];
+                n += 1;
+                i = skip_run(a, i);
+                j = skip_run(b, j);
+            }
+        }
+    }
+    n
+}
+
+/// Writes the keys of sorted `a` that are absent from sorted `b` into `out`
+///
+/// The companion of [`intersect_sorted`] for removals: a registry minus the
+/// keys being deregistered. Input and output requirements are the same,
+/// and the walk is one pass over both lists.
+///
+/// # Panics
+///
+/// Panics if `out` is too short. `a.len()` keys always suffice.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::difference_sorted;
+///
+/// let registry = [[1u8; 32], [3u8; 32], [5u8; 32]];
+/// let removed = [[3u8; 32], [4u8; 32]];
+/// let mut out = [[0u8; 32]; 3];
+///
+/// let n = difference_sorted(&registry, &removed, &mut out);
+/// assert_eq!(&out[..n], &[[1u8; 32], [5u8; 32]]);
+/// ```
+#[inline]
+pub fn difference_sorted<K: Key + Copy>(a: &[K], b: &[K], out: &mut [K]) -> usize {
+    let (mut i, mut j, mut n) = (0, 0, 0);
+    while i < a.len() {
+        let ordering = match b.get(j) {
+            Some(y) => order(&a[i], y),
+            None => Ordering::Less,
+        };
+        match ordering {
+            Ordering::Less => {
+                out[n] = a[i];
+                n += 1;
+                i = skip_run(a, i);
+            }
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                i = skip_run(a, i);
+                j = skip_run(b, j);
+            }
+        }
+    }
+    n
+}
+
+/// Writes the keys present in either of two sorted lists into `out`
+///
+/// The companion of [`intersect_sorted`] for additions: a registry merged
+/// with the keys being registered. Input and output requirements are the
+/// same, and the walk is one pass over both lists.
+///
+/// # Panics
+///
+/// Panics if `out` is too short. `a.len() + b.len()` keys always suffice.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::union_sorted;
+///
+/// let registry = [[1u8; 32], [3u8; 32]];
+/// let added = [[2u8; 32], [3u8; 32]];
+/// let mut out = [[0u8; 32]; 4];
+///
+/// let n = union_sorted(&registry, &added, &mut out);
+/// assert_eq!(&out[..n], &[[1u8; 32], [2u8; 32], [3u8; 32]]);
+/// ```
+#[inline]
+pub fn union_sorted<K: Key + Copy>(a: &[K], b: &[K], out: &mut [K]) -> usize {
+    let (mut i, mut j, mut n) = (0, 0, 0);
+    while i < a.len() || j < b.len() {
+        let ordering = match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) => order(x, y),
+            (Some(_), None) => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+        match ordering {
+            Ordering::Less => {
+                out[n] = a[i];
+                i = skip_run(a, i);
+            }
+            Ordering::Greater => {
+                out[n] = b[j];
+                j = skip_run(b, j);
+            }
+            Ordering::Equal => {
+                out[n] = a[i];
+                i = skip_run(a, i);
+                j = skip_run(b, j);
+            }
+        }
+        n += 1;
+    }
+    n
+}
+
+/// Returns `true` if two transactions' account locks conflict
+///
+/// Locks conflict when either side writes a key the other side reads or
+/// writes; shared read-only keys do not conflict. All four lists must be
+/// sorted in byte order, as with [`is_subset_of`]. Each of the three
+/// checked pairs is one merge walk, so bundlers can test packing candidates
+/// exactly in linear time without building hash sets.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::locks_conflict;
+///
+/// let (pool, oracle, user_a, user_b) = ([1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]);
+///
+/// // Both read the oracle and write their own account: no conflict
+/// assert!(!locks_conflict(&[user_a], &[oracle], &[user_b], &[oracle]));
+/// // Both write the pool
+/// assert!(locks_conflict(&[pool, user_a], &[oracle], &[pool, user_b], &[oracle]));
+/// // One writes the oracle the other reads
+/// assert!(locks_conflict(&[oracle], &[], &[user_b], &[oracle]));
+/// ```
+#[inline]
+pub fn locks_conflict<K: Key>(
+    writable_a: &[K],
+    readonly_a: &[K],
+    writable_b: &[K],
+    readonly_b: &[K],
+) -> bool {
+    intersects(writable_a, writable_b)
+        || intersects(writable_a, readonly_b)
+        || intersects(readonly_a, writable_b)
+}
+
+/// Returns `true` if two sorted lists share a key
+#[inline(always)]
+fn intersects<K: Key>(a: &[K], b: &[K]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if fast_eq(&a[i], &b[j]) {
+            return true;
+        }
+        if key_cmp(&a[i], &b[j]) == Ordering::Less {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    false
+}
+
+/// Returns the index just past the run of keys equal to `keys[start]`
+#[inline(always)]
+fn skip_run<K: Key>(keys: &[K], start: usize) -> usize {
+    let mut end = start + 1;
+    while end < keys.len() && fast_eq(&keys[end], &keys[start]) {
+        end += 1;
+    }
+    end
+}
@@ -0,0 +1,193 @@
+//! Shadow execution of the assembly routines against the Rust reference
+//!
+//! In this paranoid mode each check runs both the assembly routine and a
+//! plain byte comparison. The two must always agree; if they ever do not,
+//! the divergence is appended to a caller-provided scratch account so that
+//! security teams running staging deployments get a forensic trail, and the
+//! reference result is the one acted on.
+//!
+//! ## Scratch Account Layout
+//!
+//! | Offset | Size | Field                                    |
+//! |--------|------|------------------------------------------|
+//! | 0      | 8    | Total divergences recorded (u64, LE)     |
+//! | 8      | 32*n | Ring buffer of [`DivergenceRecord`]s     |
+//!
+//! Records are written at `total % capacity`, so the most recent divergences
+//! are kept once the buffer is full. Like any account write, a record only
+//! persists if the instruction that wrote it succeeds.
+//!
+//! ```rust,ignore
+//! let slot = Clock::get()?.slot;
+//! let mut scratch = scratch_account.try_borrow_mut_data()?;
+//! let mut shadow = ShadowRecorder::new(&mut scratch, slot)?;
+//! shadow.require_keys_eq(vault.authority(), authority.key, FailureMode::Error(1))?;
+//! ```
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError};
+
+use crate::{
+    FailureMode, Key, fast_eq, fingerprint,
+    require::{keys_eq_failed, keys_neq_failed},
+    routine,
+};
+
+/// Size of the scratch account header holding the divergence count
+pub const SHADOW_HEADER_SIZE: usize = 8;
+
+/// Size of one encoded [`DivergenceRecord`]
+pub const DIVERGENCE_RECORD_SIZE: usize = 32;
+
+/// One recorded disagreement between an assembly routine and the reference
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DivergenceRecord {
+    /// Routine that diverged, see [`routine`](crate::routine)
+    pub routine: u8,
+    /// [`fingerprint`] of the left-hand key
+    pub lhs_hash: u64,
+    /// [`fingerprint`] of the right-hand key
+    pub rhs_hash: u64,
+    /// Slot the divergence was observed in
+    pub slot: u64,
+}
+
+impl DivergenceRecord {
+    /// Encodes the record as stored in the scratch account
+    pub fn to_bytes(&self) -> [u8; DIVERGENCE_RECORD_SIZE] {
+        let mut out = [0u8; DIVERGENCE_RECORD_SIZE];
+        out[0] = self.routine;
+        out[8..16].copy_from_slice(&self.lhs_hash.to_le_bytes());
+        out[16..24].copy_from_slice(&self.rhs_hash.to_le_bytes());
+        out[24..32].copy_from_slice(&self.slot.to_le_bytes());
+        out
+    }
+
+    /// Decodes a record previously written by [`DivergenceRecord::to_bytes`]
+    pub fn from_bytes(bytes: &[u8; DIVERGENCE_RECORD_SIZE]) -> Self {
+        let word = |at: usize| u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap());
+        DivergenceRecord {
+            routine: bytes[0],
+            lhs_hash: word(8),
+            rhs_hash: word(16),
+            slot: word(24),
+        }
+    }
+}
+
+/// Runs checks through both the assembly and reference paths, recording any
+/// divergence into scratch account data
+pub struct ShadowRecorder<'a> {
+    data: &'a mut [u8],
+    slot: u64,
+}
+
+impl<'a> ShadowRecorder<'a> {
+    /// Wraps scratch account data, recording divergences at `slot`
+    ///
+    /// Fails with `AccountDataTooSmall` unless the data can hold the header
+    /// and at least one record.
+    pub fn new(data: &'a mut [u8], slot: u64) -> Result<Self, ProgramError> {
+        if data.len() < SHADOW_HEADER_SIZE + DIVERGENCE_RECORD_SIZE {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        Ok(ShadowRecorder { data, slot })
+    }
+
+    /// Total divergences recorded in this scratch account, ever
+    pub fn total(&self) -> u64 {
+        u64::from_le_bytes(self.data[..SHADOW_HEADER_SIZE].try_into().unwrap())
+    }
+
+    /// Number of records the ring buffer holds
+    pub fn capacity(&self) -> usize {
+        (self.data.len() - SHADOW_HEADER_SIZE) / DIVERGENCE_RECORD_SIZE
+    }
+
+    /// Returns the record in ring slot `index`, if that slot has been written
+    pub fn record(&self, index: usize) -> Option<DivergenceRecord> {
+        if index >= self.capacity() || index as u64 >= self.total() {
+            return None;
+        }
+        let at = SHADOW_HEADER_SIZE + index * DIVERGENCE_RECORD_SIZE;
+        let bytes = self.data[at..at + DIVERGENCE_RECORD_SIZE]
+            .try_into()
+            .unwrap();
+        Some(DivergenceRecord::from_bytes(bytes))
+    }
+
+    #[cold]
+    fn record_divergence(&mut self, routine: u8, lhs: &[u8; 32], rhs: &[u8; 32]) {
+        let total = self.total();
+        let record = DivergenceRecord {
+            routine,
+            lhs_hash: fingerprint(lhs),
+            rhs_hash: fingerprint(rhs),
+            slot: self.slot,
+        };
+        let at =
+            SHADOW_HEADER_SIZE + (total % self.capacity() as u64) as usize * DIVERGENCE_RECORD_SIZE;
+        self.data[at..at + DIVERGENCE_RECORD_SIZE].copy_from_slice(&record.to_bytes());
+        self.data[..SHADOW_HEADER_SIZE].copy_from_slice(&total.wrapping_add(1).to_le_bytes());
+    }
+
+    /// Compares `asm` (the assembly result) to the reference result for
+    /// `lhs == rhs`, recording a divergence and returning the reference
+    #[inline(always)]
+    fn reconcile<K: Key>(&mut self, routine: u8, asm: bool, lhs: &K, rhs: &K) -> bool {
+        let reference = lhs.key_bytes() == rhs.key_bytes();
+        if asm != reference {
+            self.record_divergence(routine, lhs.key_bytes(), rhs.key_bytes());
+        }
+        reference
+    }
+
+    /// [`fast_eq`], shadowed by the reference comparison
+    #[inline]
+    pub fn fast_eq<K: Key>(&mut self, lhs: &K, rhs: &K) -> bool {
+        let asm = fast_eq(lhs, rhs);
+        self.reconcile(routine::FAST_EQ, asm, lhs, rhs)
+    }
+
+    /// [`fast_eq_branchless`](crate::fast_eq_branchless), shadowed by the
+    /// reference comparison
+    #[cfg(feature = "cmp")]
+    #[inline]
+    pub fn fast_eq_branchless<K: Key>(&mut self, lhs: &K, rhs: &K) -> bool {
+        let asm = crate::fast_eq_branchless(lhs, rhs);
+        self.reconcile(routine::FAST_EQ_BRANCHLESS, asm, lhs, rhs)
+    }
+
+    /// [`require_keys_eq`](crate::require_keys_eq) using the shadowed
+    /// comparison
+    #[inline]
+    #[track_caller]
+    pub fn require_keys_eq<K: Key>(
+        &mut self,
+        lhs: &K,
+        rhs: &K,
+        mode: FailureMode,
+    ) -> ProgramResult {
+        if self.fast_eq(lhs, rhs) {
+            Ok(())
+        } else {
+            Err(keys_eq_failed(lhs, rhs, mode))
+        }
+    }
+
+    /// [`require_keys_neq`](crate::require_keys_neq) using the shadowed
+    /// comparison
+    #[inline]
+    #[track_caller]
+    pub fn require_keys_neq<K: Key>(
+        &mut self,
+        lhs: &K,
+        rhs: &K,
+        mode: FailureMode,
+    ) -> ProgramResult {
+        if self.fast_eq(lhs, rhs) {
+            Err(keys_neq_failed(lhs, mode))
+        } else {
+            Ok(())
+        }
+    }
+}
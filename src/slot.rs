@@ -0,0 +1,90 @@
+//! Updates to key slots stored in account data
+
+#[cfg(feature = "solana-program")]
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError};
+
+use crate::Key;
+#[cfg(feature = "solana-program")]
+use crate::{FailureMode, diag::log_failure, fast_eq};
+
+/// Overwrites `slot` with `new` if it currently holds `expected`, returning
+/// whether it did
+///
+/// The standard authority-rotation primitive: a program runs its
+/// instructions one at a time, so nothing can change the slot between the
+/// check and the write. On Solana both happen in one assembly routine that
+/// compares with early exit and then copies word by word.
+///
+/// ```rust
+/// use solana_pubkey_compare::key_cas;
+///
+/// let (old, new) = ([1u8; 32], [2u8; 32]);
+/// let mut authority = old;
+/// assert!(key_cas(&mut authority, &old, &new));
+/// assert!(!key_cas(&mut authority, &old, &[3u8; 32]));
+/// assert_eq!(authority, new);
+/// ```
+#[inline(always)]
+pub fn key_cas<K: Key>(slot: &mut [u8; 32], expected: &K, new: &K) -> bool {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        crate::__solana_pubkey_compare__key_cas(
+            slot.as_mut_ptr(),
+            expected.key_ptr(),
+            new.key_ptr(),
+        )
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        if slot != expected.key_bytes() {
+            return false;
+        }
+        *slot = *new.key_bytes();
+        true
+    }
+}
+
+/// Writes `key` into `slot` if the slot is still all zero, and otherwise
+/// requires the slot to hold `key`
+///
+/// Encapsulates "set on first use, verify thereafter": the zero check and
+/// the write are one [`key_cas`] against the zero key, and only an already
+/// initialized slot pays for the second comparison.
+///
+/// An all-zero `key` always fails through `mode`: writing it would leave the
+/// slot uninitialized, so the next caller could claim it with any key.
+///
+/// ```rust
+/// use solana_pubkey_compare::{FailureMode, init_or_require};
+///
+/// let mut owner = [0u8; 32];
+/// assert!(init_or_require(&mut owner, &[5u8; 32], FailureMode::Error(1)).is_ok());
+/// assert!(init_or_require(&mut owner, &[5u8; 32], FailureMode::Error(1)).is_ok());
+/// assert!(init_or_require(&mut owner, &[6u8; 32], FailureMode::Error(1)).is_err());
+///
+/// let mut unset = [0u8; 32];
+/// assert!(init_or_require(&mut unset, &[0u8; 32], FailureMode::Error(1)).is_err());
+/// ```
+#[cfg(feature = "solana-program")]
+#[inline(always)]
+#[track_caller]
+pub fn init_or_require<K: Key>(slot: &mut [u8; 32], key: &K, mode: FailureMode) -> ProgramResult {
+    let key_bytes = key.key_bytes();
+    if !fast_eq(key_bytes, &[0u8; 32])
+        && (key_cas(slot, &[0u8; 32], key_bytes) || fast_eq(slot, key_bytes))
+    {
+        Ok(())
+    } else {
+        Err(init_or_require_failed(slot, key, mode))
+    }
+}
+
+/// Failure path of [`init_or_require`]
+#[cfg(feature = "solana-program")]
+#[cold]
+#[track_caller]
+fn init_or_require_failed<K: Key>(slot: &[u8; 32], key: &K, mode: FailureMode) -> ProgramError {
+    log_failure("init_or_require", None, &[slot, key.key_bytes()]);
+    mode.fail("init_or_require")
+}
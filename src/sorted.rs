@@ -0,0 +1,206 @@
+//! Searches and updates over sorted key arrays
+//!
+//! Keys are ordered lexicographically by their bytes, matching `Pubkey`'s
+//! `Ord`. With the `cmp` feature each comparison runs the [`fast_cmp`]
+//! assembly routine, which resolves on the first differing 8-byte chunk.
+//!
+//! [`insert_sorted`] and [`remove_sorted`] maintain such an array in place
+//! inside account data:
+//! a key region of `data.len() / 32` slots, of which the first `count` are
+//! in use, with `count` stored elsewhere in the account.
+//!
+//! [`fast_cmp`]: crate::fast_cmp
+
+use core::cmp::Ordering;
+
+use crate::{CompareError, Key, parse::keys_from_instruction_data};
+
+/// Byte order of two keys, through the `fast_cmp` routine when it is linked
+#[inline(always)]
+pub(crate) fn order<K: Key>(lhs: &K, rhs: &K) -> Ordering {
+    #[cfg(feature = "cmp")]
+    {
+        crate::fast_cmp(lhs, rhs)
+    }
+
+    #[cfg(not(feature = "cmp"))]
+    {
+        crate::set::key_cmp(lhs, rhs)
+    }
+}
+
+/// Length above which [`sort_keys`] switches from binary insertion to the
+/// core unstable sort
+const INSERTION_SORT_MAX: usize = 64;
+
+/// Sorts `keys` in place into byte order
+///
+/// Tuned for the 2 to 64 key arrays of on-chain configuration: each key is
+/// placed by binary search over the sorted prefix, so a slice of `n` keys
+/// takes about `n log2 n` comparisons, and the shift is a single
+/// `rotate_right`. Longer slices use `sort_unstable_by` with the same
+/// comparator. Not stable, which is unobservable for equal keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::sort_keys;
+///
+/// let mut keys = [[3u8; 32], [1u8; 32], [2u8; 32]];
+/// sort_keys(&mut keys);
+/// assert_eq!(keys, [[1u8; 32], [2u8; 32], [3u8; 32]]);
+/// ```
+#[inline]
+pub fn sort_keys<K: Key>(keys: &mut [K]) {
+    if keys.len() > INSERTION_SORT_MAX {
+        keys.sort_unstable_by(order);
+        return;
+    }
+    for i in 1..keys.len() {
+        let (sorted, rest) = keys.split_at(i);
+        let at = sorted.partition_point(|key| order(key, &rest[0]).is_le());
+        keys[at..=i].rotate_right(1);
+    }
+}
+
+/// Returns `true` if `keys` is in non-decreasing byte order
+///
+/// The runtime counterpart of [`const_keys_sorted`], for validating a
+/// client-supplied key list before [`binary_search_keys`] relies on its
+/// order. Stops at the first out-of-order pair.
+///
+/// [`const_keys_sorted`]: crate::const_keys_sorted
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::is_sorted;
+///
+/// assert!(is_sorted(&[[1u8; 32], [1u8; 32], [4u8; 32]]));
+/// assert!(!is_sorted(&[[4u8; 32], [1u8; 32]]));
+/// ```
+#[inline]
+pub fn is_sorted<K: Key>(keys: &[K]) -> bool {
+    keys.windows(2)
+        .all(|pair| order(&pair[0], &pair[1]).is_le())
+}
+
+/// Binary searches a sorted key slice for `target`
+///
+/// Returns `Ok(index)` of a matching key, or `Err(index)` of the position
+/// where `target` could be inserted to keep the slice sorted, like
+/// `slice::binary_search`. If several keys match, any one of their indices
+/// may be returned. The result is unspecified if `sorted` is not sorted.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::binary_search_keys;
+///
+/// let allowlist = [[1u8; 32], [3u8; 32], [5u8; 32]];
+/// assert_eq!(binary_search_keys(&allowlist, &[3u8; 32]), Ok(1));
+/// assert_eq!(binary_search_keys(&allowlist, &[4u8; 32]), Err(2));
+/// ```
+#[inline]
+pub fn binary_search_keys<K: Key>(sorted: &[K], target: &K) -> Result<usize, usize> {
+    let (mut lo, mut hi) = (0, sorted.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match order(&sorted[mid], target) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return Ok(mid),
+        }
+    }
+    Err(lo)
+}
+
+/// Inserts `key` into the sorted key region `data`, returning its index
+///
+/// `data` holds `data.len() / 32` key slots, the first `count` of which are
+/// sorted and in use. The insertion point is found with
+/// [`binary_search_keys`], the keys after it are shifted up one slot with a
+/// single `copy_within` (a `sol_memmove_` syscall on Solana), and `count`
+/// is incremented.
+///
+/// Fails with `DuplicateKey` (both positions set to the existing key's
+/// index) if `key` is already present, `RegionFull` if every slot is in
+/// use, and `LengthInvalid` if `count` slots do not fit in `data`. Nothing
+/// is modified on failure.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::insert_sorted;
+///
+/// let mut region = [0u8; 32 * 4];
+/// let mut count = 0;
+/// assert_eq!(insert_sorted(&mut region, &mut count, &[5u8; 32]), Ok(0));
+/// assert_eq!(insert_sorted(&mut region, &mut count, &[2u8; 32]), Ok(0));
+/// assert_eq!(count, 2);
+/// assert_eq!(region[32..64], [5u8; 32]);
+/// ```
+#[inline]
+pub fn insert_sorted<K: Key>(
+    data: &mut [u8],
+    count: &mut u32,
+    key: &K,
+) -> Result<usize, CompareError> {
+    let len = *count as usize;
+    let keys = keys_from_instruction_data(data, len)?;
+    let index = match binary_search_keys(keys, key.key_bytes()) {
+        Ok(i) => return Err(CompareError::DuplicateKey { i, j: i }),
+        Err(index) => index,
+    };
+    if data.len() / 32 <= len {
+        return Err(CompareError::RegionFull);
+    }
+
+    let at = index * 32;
+    data.copy_within(at..len * 32, at + 32);
+    data[at..at + 32].copy_from_slice(key.key_bytes());
+    *count += 1;
+    Ok(index)
+}
+
+/// Removes `key` from the sorted key region `data`, returning its former
+/// index
+///
+/// The counterpart of [`insert_sorted`]: the key is located with
+/// [`binary_search_keys`], the keys after it are shifted down one slot with
+/// a single `copy_within`, the vacated last slot is zeroed and `count` is
+/// decremented.
+///
+/// Fails with `NotFound` if `key` is not present, and `LengthInvalid` if
+/// `count` slots do not fit in `data`. Nothing is modified on failure.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::{insert_sorted, remove_sorted};
+///
+/// let mut region = [0u8; 32 * 4];
+/// let mut count = 0;
+/// insert_sorted(&mut region, &mut count, &[2u8; 32]).unwrap();
+/// insert_sorted(&mut region, &mut count, &[5u8; 32]).unwrap();
+///
+/// assert_eq!(remove_sorted(&mut region, &mut count, &[2u8; 32]), Ok(0));
+/// assert_eq!(count, 1);
+/// assert_eq!(region[..32], [5u8; 32]);
+/// ```
+#[inline]
+pub fn remove_sorted<K: Key>(
+    data: &mut [u8],
+    count: &mut u32,
+    key: &K,
+) -> Result<usize, CompareError> {
+    let len = *count as usize;
+    let keys = keys_from_instruction_data(data, len)?;
+    let index = binary_search_keys(keys, key.key_bytes()).map_err(|_| CompareError::NotFound)?;
+
+    let at = index * 32;
+    data.copy_within(at + 32..len * 32, at);
+    data[(len - 1) * 32..len * 32].fill(0);
+    *count -= 1;
+    Ok(index)
+}
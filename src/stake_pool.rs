@@ -0,0 +1,85 @@
+//! SPL stake-pool validator list lookups over raw account data
+//!
+//! A validator list holds one 73-byte `ValidatorStakeInfo` per validator,
+//! often hundreds of them, so deserializing it or comparing every entry in
+//! full can exhaust the compute budget. These helpers scan the packed
+//! entries in place with [`scan_keys`], which on Solana costs 4 instructions
+//! per entry whose vote account differs in its first word.
+//!
+//! ## Account Layout
+//!
+//! | Offset | Size  | Field                                      |
+//! |--------|-------|--------------------------------------------|
+//! | 0      | 1     | Account type                               |
+//! | 1      | 4     | Maximum validators (u32, LE)               |
+//! | 5      | 4     | Number of entries (u32, LE)                |
+//! | 9      | 73*n  | Entries; vote account at offset 41 in each |
+
+use crate::{CompareError, Key, scan::scan_keys};
+
+/// Byte offset of the entry count
+pub const VALIDATOR_COUNT_OFFSET: usize = 5;
+
+/// Byte offset of the first entry
+pub const VALIDATOR_ENTRIES_OFFSET: usize = 9;
+
+/// Size of one `ValidatorStakeInfo` entry
+pub const VALIDATOR_ENTRY_SIZE: usize = 73;
+
+/// Byte offset of the vote account address within an entry
+pub const VOTE_ACCOUNT_OFFSET: usize = 41;
+
+/// Returns the number of entries in validator list data
+///
+/// Fails with `LengthInvalid` if the data is too short for its header or for
+/// the entries it claims to hold.
+#[inline]
+pub fn validator_count(list_data: &[u8]) -> Result<usize, CompareError> {
+    let count = list_data
+        .get(VALIDATOR_COUNT_OFFSET..VALIDATOR_ENTRIES_OFFSET)
+        .ok_or(CompareError::LengthInvalid)?;
+    let count = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+    if (list_data.len() - VALIDATOR_ENTRIES_OFFSET) / VALIDATOR_ENTRY_SIZE < count {
+        return Err(CompareError::LengthInvalid);
+    }
+    Ok(count)
+}
+
+/// Byte offset of entry `index` within validator list data
+#[inline(always)]
+pub const fn validator_entry_offset(index: usize) -> usize {
+    VALIDATOR_ENTRIES_OFFSET + index * VALIDATOR_ENTRY_SIZE
+}
+
+/// Returns the index of the entry whose vote account is `vote_account`
+///
+/// Only the first [`validator_count`] entries are searched; space past them
+/// can hold stale entries of removed validators. Fails with `NotFound` if no
+/// entry matches, or `LengthInvalid` if the data is malformed.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::stake_pool::{
+///     VOTE_ACCOUNT_OFFSET, find_validator, validator_entry_offset,
+/// };
+///
+/// let mut list = vec![0u8; validator_entry_offset(3)];
+/// list[5] = 3;
+/// let vote = validator_entry_offset(1) + VOTE_ACCOUNT_OFFSET;
+/// list[vote..vote + 32].copy_from_slice(&[4u8; 32]);
+///
+/// assert_eq!(find_validator(&list, &[4u8; 32]), Ok(1));
+/// ```
+#[inline]
+pub fn find_validator<K: Key>(list_data: &[u8], vote_account: &K) -> Result<usize, CompareError> {
+    let count = validator_count(list_data)?;
+    let entries = &list_data[..validator_entry_offset(count)];
+    scan_keys(
+        entries,
+        VALIDATOR_ENTRIES_OFFSET + VOTE_ACCOUNT_OFFSET,
+        VALIDATOR_ENTRY_SIZE,
+        vote_account,
+    )
+    .ok_or(CompareError::NotFound)
+}
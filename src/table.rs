@@ -0,0 +1,88 @@
+//! Sorted static key tables with binary-search lookup
+//!
+//! A [`KeyTable`] wraps a sorted `&'static [[u8; 32]]`, typically an
+//! allowlist generated from a data file by `include_keys!` (with the
+//! `macros` feature). Sortedness is checked when the table is built in a
+//! `const` or `static`, so lookups can never run over an unsorted table.
+
+use core::cmp::Ordering;
+
+use crate::{Key, const_keys_sorted, fast_eq};
+
+/// A sorted table of keys supporting `O(log n)` membership tests
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyTable<'a> {
+    keys: &'a [[u8; 32]],
+}
+
+impl<'a> KeyTable<'a> {
+    /// Wraps `keys`, which must be in non-decreasing byte-lexicographic order
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keys` is not sorted. In a `const` or `static` initializer
+    /// this is a compile error.
+    pub const fn new(keys: &'a [[u8; 32]]) -> Self {
+        assert!(const_keys_sorted(keys), "KeyTable keys must be sorted");
+        KeyTable { keys }
+    }
+
+    /// Returns the sorted keys
+    #[inline(always)]
+    pub const fn keys(&self) -> &'a [[u8; 32]] {
+        self.keys
+    }
+
+    /// Returns the number of keys in the table
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the table holds no keys
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the position of `key` in the table, if present
+    ///
+    /// Each probe orders by the first big-endian word alone and only falls
+    /// back to the remaining bytes when those match, which for uniformly
+    /// distributed keys is only at the final probe.
+    #[inline]
+    pub fn index_of<K: Key>(&self, key: &K) -> Option<usize> {
+        let key = key.key_bytes();
+        let prefix = prefix_word(key);
+        let (mut lo, mut hi) = (0, self.keys.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let probe = &self.keys[mid];
+            let ordering = match prefix_word(probe).cmp(&prefix) {
+                Ordering::Equal if fast_eq(probe, key) => return Some(mid),
+                Ordering::Equal => probe[8..].cmp(&key[8..]),
+                ordering => ordering,
+            };
+            if ordering == Ordering::Less {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if `key` is in the table
+    #[inline]
+    pub fn contains<K: Key>(&self, key: &K) -> bool {
+        self.index_of(key).is_some()
+    }
+}
+
+/// First eight key bytes as a big-endian word, ordered like the bytes
+#[inline(always)]
+fn prefix_word(key: &[u8; 32]) -> u64 {
+    u64::from_be_bytes([
+        key[0], key[1], key[2], key[3], key[4], key[5], key[6], key[7],
+    ])
+}
@@ -0,0 +1,128 @@
+//! Production telemetry for rollouts across large programs
+//!
+//! [`Telemetry`] wraps a designated stats account and exposes the crate's
+//! checks as methods that also bump per-routine counters, so teams can
+//! measure how often each fast path and failure branch runs in production.
+//!
+//! ## Stats Account Layout
+//!
+//! One 16-byte entry per [`routine`] identifier, indexed by that id:
+//!
+//! | Offset        | Size | Field                  |
+//! |---------------|------|------------------------|
+//! | 16 * id       | 8    | Invocations (u64, LE)  |
+//! | 16 * id + 8   | 8    | Failures (u64, LE)     |
+//!
+//! A comparison "fails" when it returns `false`; a `require_*` check fails
+//! when it returns an error. The runtime discards account writes of failed
+//! instructions, so failure counts only persist where the program recovers
+//! from the failure and the instruction still succeeds. Routines whose entry
+//! lies beyond the end of the account are not counted, so an account sized
+//! for an older build keeps working as routines are added.
+//!
+//! ```rust,ignore
+//! let mut stats = stats_account.try_borrow_mut_data()?;
+//! let mut telemetry = Telemetry::new(&mut stats);
+//! telemetry.require_keys_eq(authority.key, &config.authority, FailureMode::Error(1))?;
+//! ```
+
+use solana_program::entrypoint::ProgramResult;
+
+use crate::{FailureMode, Key, fast_eq, require_keys_eq, require_keys_neq, routine};
+
+/// Size of one routine's counter entry
+pub const TELEMETRY_ENTRY_SIZE: usize = 16;
+
+/// Stats account size covering every routine this build knows about
+pub const TELEMETRY_ACCOUNT_SIZE: usize = routine::COUNT * TELEMETRY_ENTRY_SIZE;
+
+/// Counts invocations and failures of the crate's checks in account data
+pub struct Telemetry<'a> {
+    data: &'a mut [u8],
+}
+
+impl<'a> Telemetry<'a> {
+    /// Wraps stats account data, see the module docs for the layout
+    pub fn new(data: &'a mut [u8]) -> Self {
+        Telemetry { data }
+    }
+
+    /// Adds `delta` to the counter at byte `at`, if it lies within the data
+    #[inline(always)]
+    fn add(&mut self, at: usize, delta: u64) {
+        if let Some(counter) = self.data.get_mut(at..at + 8) {
+            let value = u64::from_le_bytes(counter.try_into().unwrap());
+            counter.copy_from_slice(&value.wrapping_add(delta).to_le_bytes());
+        }
+    }
+
+    /// Reads the counter at byte `at`, or 0 if it lies beyond the data
+    fn get(&self, at: usize) -> u64 {
+        self.data
+            .get(at..at + 8)
+            .map_or(0, |counter| u64::from_le_bytes(counter.try_into().unwrap()))
+    }
+
+    /// Records one invocation of `routine`, and one failure if `failed`
+    #[inline]
+    pub fn record(&mut self, routine: u8, failed: bool) {
+        let at = routine as usize * TELEMETRY_ENTRY_SIZE;
+        self.add(at, 1);
+        self.add(at + 8, failed as u64);
+    }
+
+    /// Total recorded invocations of `routine`
+    pub fn invocations(&self, routine: u8) -> u64 {
+        self.get(routine as usize * TELEMETRY_ENTRY_SIZE)
+    }
+
+    /// Total recorded failures of `routine`
+    pub fn failures(&self, routine: u8) -> u64 {
+        self.get(routine as usize * TELEMETRY_ENTRY_SIZE + 8)
+    }
+
+    /// [`fast_eq`], counted
+    #[inline]
+    pub fn fast_eq<K: Key>(&mut self, lhs: &K, rhs: &K) -> bool {
+        let equal = fast_eq(lhs, rhs);
+        self.record(routine::FAST_EQ, !equal);
+        equal
+    }
+
+    /// [`fast_eq_branchless`](crate::fast_eq_branchless), counted
+    #[cfg(feature = "cmp")]
+    #[inline]
+    pub fn fast_eq_branchless<K: Key>(&mut self, lhs: &K, rhs: &K) -> bool {
+        let equal = crate::fast_eq_branchless(lhs, rhs);
+        self.record(routine::FAST_EQ_BRANCHLESS, !equal);
+        equal
+    }
+
+    /// [`require_keys_eq`], counted
+    #[inline]
+    #[track_caller]
+    pub fn require_keys_eq<K: Key>(
+        &mut self,
+        lhs: &K,
+        rhs: &K,
+        mode: FailureMode,
+    ) -> ProgramResult {
+        let result = require_keys_eq(lhs, rhs, mode);
+        self.record(routine::REQUIRE_KEYS_EQ, result.is_err());
+        result
+    }
+
+    /// [`require_keys_neq`], counted
+    #[inline]
+    #[track_caller]
+    pub fn require_keys_neq<K: Key>(
+        &mut self,
+        lhs: &K,
+        rhs: &K,
+        mode: FailureMode,
+    ) -> ProgramResult {
+        let result = require_keys_neq(lhs, rhs, mode);
+        self.record(routine::REQUIRE_KEYS_NEQ, result.is_err());
+        result
+    }
+}
@@ -0,0 +1,57 @@
+//! SPL Token account checks over raw account data
+//!
+//! Like [`mint`](crate::mint), these read token account fields in place
+//! instead of unpacking the account, and work on Token-2022 accounts, whose
+//! base layout is the same. The account owner is not checked.
+
+use crate::{CompareError, Key, fast_eq, mint::coption_key};
+
+/// Size of the base SPL Token account layout
+pub const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Byte offset of the mint
+pub const TOKEN_MINT_OFFSET: usize = 0;
+
+/// Byte offset of the owner
+pub const TOKEN_OWNER_OFFSET: usize = 32;
+
+/// Byte offset of the delegate's `COption` tag
+pub const TOKEN_DELEGATE_OFFSET: usize = 72;
+
+/// Returns the delegate, or `None` if the account has none
+///
+/// Fails with `LengthInvalid` if `data` is shorter than
+/// [`TOKEN_ACCOUNT_LEN`] or the option tag is neither 0 nor 1.
+#[inline]
+pub fn delegate(data: &[u8]) -> Result<Option<&[u8; 32]>, CompareError> {
+    coption_key(data, TOKEN_ACCOUNT_LEN, TOKEN_DELEGATE_OFFSET)
+}
+
+/// Returns `true` if the account's delegate is exactly `expected`
+///
+/// `None` matches only an account with no delegate, decided by the option
+/// tag alone: a delegate set to the all-zero key is still a delegate, and
+/// the stale key bytes left behind by a revoked delegate are ignored.
+/// Malformed data never matches.
+///
+/// # Examples
+///
+/// ```rust
+/// use solana_pubkey_compare::token::{TOKEN_ACCOUNT_LEN, token_account_has_delegate};
+///
+/// let mut account = [0u8; TOKEN_ACCOUNT_LEN];
+/// assert!(token_account_has_delegate(&account, None::<&[u8; 32]>));
+///
+/// // Delegate set to the zero key
+/// account[72] = 1;
+/// assert!(!token_account_has_delegate(&account, None::<&[u8; 32]>));
+/// assert!(token_account_has_delegate(&account, Some(&[0u8; 32])));
+/// ```
+#[inline]
+pub fn token_account_has_delegate<K: Key>(data: &[u8], expected: Option<&K>) -> bool {
+    match (delegate(data), expected) {
+        (Ok(None), None) => true,
+        (Ok(Some(delegate)), Some(expected)) => fast_eq(delegate, expected.key_bytes()),
+        _ => false,
+    }
+}
@@ -0,0 +1,69 @@
+//! Guards the assembly instruction budget of the minimal
+//! (`default-features = false`) build
+//!
+//! This is a source-level budget, not an object size: it counts the
+//! instructions written in the `.s` files that the configuration assembles,
+//! at one 8-byte slot each except `lddw`, which takes two. The Rust
+//! wrappers, relocations and anything else LLVM emits into the linked
+//! program are not counted, so it catches routines leaking into the minimal
+//! build rather than measuring the final `.so`; `bench/minimal` checks that.
+//!
+//! Under `opt-size` the sources `build.rs` substitutes are the ones counted.
+
+include!("../src/asm/sources.rs");
+
+/// Instruction bytes the minimal configuration may assemble: `fast_eq` alone
+const MINIMAL_SOURCE_BUDGET: usize = 144;
+
+/// Encoded size in bytes of the instructions in an assembly source
+fn encoded_size(source: &str) -> usize {
+    source
+        .lines()
+        .map(|line| line.split("//").next().unwrap().trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('.') && !line.ends_with(':'))
+        .map(|line| if line.starts_with("lddw ") { 16 } else { 8 })
+        .sum()
+}
+
+/// Returns the source `build.rs` assembles for `path` in this configuration
+fn selected_source(path: &str) -> String {
+    select_source(path, cfg!(feature = "opt-size"))
+}
+
+fn read_source(path: &str) -> String {
+    let path = format!("{}/{path}", env!("CARGO_MANIFEST_DIR"));
+    std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("{path}: {err}"))
+}
+
+#[test]
+fn minimal_configuration_fits_source_budget() {
+    let size: usize = ASM_SOURCES
+        .iter()
+        .filter(|(feature, _)| feature.is_none())
+        .map(|(_, path)| encoded_size(&read_source(&selected_source(path))))
+        .sum();
+
+    assert!(
+        size <= MINIMAL_SOURCE_BUDGET,
+        "minimal configuration assembles {size} instruction bytes, over {MINIMAL_SOURCE_BUDGET}"
+    );
+}
+
+#[test]
+fn gated_sources_use_declared_features() {
+    let manifest = read_source("Cargo.toml");
+
+    for (feature, path) in ASM_SOURCES {
+        let path = selected_source(path);
+        assert!(
+            encoded_size(&read_source(&path)) > 0,
+            "{path} has no instructions"
+        );
+        if let Some(feature) = feature {
+            assert!(
+                manifest.contains(&format!("\n{feature} = ")),
+                "{path} is gated on undeclared feature `{feature}`"
+            );
+        }
+    }
+}
@@ -0,0 +1,1011 @@
+//! Deterministic randomized stress tests
+//!
+//! Every scan and set API is run over seeded-random workloads and checked
+//! against a naive reference implementation. Workload profiles control how
+//! often keys repeat and how often distinct keys share long prefixes, which
+//! exercises the early-exit and prefilter paths that uniform random keys
+//! almost never reach.
+#![cfg(all(feature = "cmp", feature = "scan"))]
+
+use std::collections::BTreeSet;
+
+use solana_pubkey_compare::{
+    FastOrd, Hint, KeyTable, MultiBufferSearch, PubkeySet, all_equal, all_unique,
+    all_unique_in_place, alt, any_eq, binary_search_keys, contains, count_eq, count_members, ct_eq,
+    dedup_in_place, diff_index, difference_sorted, eq_either, eq_pairs, fast_assert_eq,
+    fast_assert_eq_with_code, fast_cmp, fast_eq, fast_eq_8, fast_eq_64, fast_eq_at,
+    fast_eq_branchless, fast_eq_hint, fast_eq_n, fast_eq_opt, fast_eq_ptrcheck, fast_eq_raw,
+    fast_eq_rev, fast_eq_u64, fast_eq_unaligned, fast_eq2, fast_gt, fast_is_zero, fast_lt, fast_ne,
+    find_key_in_bytes, find_zero_slot, first_diff_qword, insert_sorted, intersect_sorted,
+    is_sorted, is_subset_of, is_subset_of_unsorted, is_superset_of, locks_conflict, masked_eq,
+    max_key, min_key, opt_is, parse, partition_eq, position_of, prefix_eq, remove_sorted,
+    scan_keys, select_k_smallest, select_kth, set_eq, set_eq_in_place, slices_eq, sort_keys,
+    symmetric_difference, union_sorted, validate_keys,
+};
+
+type Key = [u8; 32];
+
+/// Iterations per profile; raise locally for longer soak runs
+const ROUNDS: usize = 300;
+
+/// Fixed seed so failures reproduce exactly
+const SEED: u64 = 0x5EED_CAFE_F00D_BEEF;
+
+/// SplitMix64: small, seedable and good enough for workload generation
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn percent(&mut self, p: u32) -> bool {
+        self.below(100) < p as usize
+    }
+
+    fn bytes(&mut self, out: &mut [u8]) {
+        for b in out {
+            *b = self.next() as u8;
+        }
+    }
+}
+
+/// Shape of a generated key population
+#[derive(Clone, Copy, Debug)]
+struct Profile {
+    /// Chance (percent) that a key repeats one already generated
+    dup_rate: u32,
+    /// Chance (percent) that a fresh key copies a prefix of an earlier key
+    prefix_share: u32,
+}
+
+#[rustfmt::skip]
+const PROFILES: &[Profile] = &[
+    Profile { dup_rate: 0, prefix_share: 0 },
+    Profile { dup_rate: 30, prefix_share: 0 },
+    Profile { dup_rate: 10, prefix_share: 80 },
+    Profile { dup_rate: 60, prefix_share: 60 },
+];
+
+fn gen_keys(rng: &mut Rng, n: usize, profile: Profile) -> Vec<Key> {
+    let mut keys: Vec<Key> = Vec::with_capacity(n);
+    while keys.len() < n {
+        if !keys.is_empty() && rng.percent(profile.dup_rate) {
+            let k = keys[rng.below(keys.len())];
+            keys.push(k);
+            continue;
+        }
+        let mut key = [0u8; 32];
+        rng.bytes(&mut key);
+        if !keys.is_empty() && rng.percent(profile.prefix_share) {
+            // Share 8, 16 or 24 leading bytes, or all but the last byte
+            let shared = [8, 16, 24, 31][rng.below(4)];
+            key[..shared].copy_from_slice(&keys[rng.below(keys.len())][..shared]);
+        }
+        keys.push(key);
+    }
+    keys
+}
+
+/// Picks a probe key: usually from `pool`, sometimes a near miss, sometimes
+/// fresh
+fn probe(rng: &mut Rng, pool: &[Key]) -> Key {
+    let mut key = [0u8; 32];
+    rng.bytes(&mut key);
+    if !pool.is_empty() {
+        match rng.below(3) {
+            0 => key = pool[rng.below(pool.len())],
+            1 => {
+                key = pool[rng.below(pool.len())];
+                key[rng.below(32)] ^= 1 << rng.below(8);
+            }
+            _ => {}
+        }
+    }
+    key
+}
+
+fn for_each_workload(mut f: impl FnMut(&mut Rng, Profile)) {
+    let mut rng = Rng(SEED);
+    for &profile in PROFILES {
+        for _ in 0..ROUNDS {
+            f(&mut rng, profile);
+        }
+    }
+}
+
+#[test]
+fn equality_variants_agree() {
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 2, profile);
+        let (a, b) = (keys[0], probe(rng, &keys));
+        let expected = a == b;
+        assert_eq!(fast_eq(&a, &b), expected);
+        assert_eq!(fast_eq_branchless(&a, &b), expected);
+        assert_eq!(fast_eq_hint(&a, &b, Hint::UsuallyEqual), expected);
+        assert_eq!(fast_eq_hint(&a, &b, Hint::UsuallyDifferent), expected);
+        assert_eq!(fast_eq_hint(&a, &b, Hint::DiffersAtEnd), expected);
+        assert_eq!(fast_eq_hint(&a, &b, Hint::ConstantCost), expected);
+        assert_eq!(fast_eq_rev(&a, &b), expected);
+        assert_eq!(fast_eq_ptrcheck(&a, &b), expected);
+        assert!(fast_eq_ptrcheck(&a, &a));
+        #[cfg(feature = "solana-pubkey")]
+        {
+            let (x, y) = (
+                solana_pubkey::Pubkey::from(a),
+                solana_pubkey::Pubkey::from(b),
+            );
+            assert_eq!(fast_eq(&x, &y), expected);
+        }
+        if expected {
+            fast_assert_eq(&a, &b);
+            fast_assert_eq_with_code(&a, &b, 6001);
+        }
+        assert_eq!(fast_eq_opt(&Some(a), &Some(b)), expected);
+        assert_eq!(opt_is(&Some(a), &b), expected);
+        assert!(!fast_eq_opt(&Some(a), &None));
+        assert!(!opt_is(&None, &b));
+        assert_eq!(fast_ne(&a, &b), !expected);
+        assert_eq!(ct_eq(&a, &b), expected);
+        assert_eq!(fast_eq_u64(&a, &b), expected as u64);
+        let c = probe(rng, &keys);
+        assert_eq!(eq_either(&a, &b, &c), a == b || a == c);
+        assert_eq!(eq_either(&a, &c, &b), a == b || a == c);
+        assert_eq!(eq_pairs(&a, &b, &keys[1], &c), a == b && keys[1] == c);
+        let n = rng.below(34);
+        assert_eq!(prefix_eq(&a, &b, n), a[..n.min(32)] == b[..n.min(32)]);
+        let mut mask = [0xFFu8; 32];
+        mask[rng.below(32)] = rng.next() as u8;
+        let masked = |key: &Key| key.iter().zip(mask).map(|(k, m)| k & m).collect::<Vec<_>>();
+        assert_eq!(masked_eq(&a, &b, &mask), masked(&a) == masked(&b));
+        assert_eq!(diff_index(&a, &b), (0..32).find(|&i| a[i] != b[i]));
+        assert_eq!(
+            first_diff_qword(&a, &b) as usize,
+            diff_index(&a, &b).map_or(4, |i| i / 8)
+        );
+        assert_eq!(fast_cmp(&a, &b), a.cmp(&b));
+        assert_eq!(fast_lt(&a, &b), a < b);
+        assert_eq!(fast_gt(&a, &b), a > b);
+
+        // Random keys are never zero; probe the zero key and single-bit
+        // neighbours instead
+        let mut zero = [0u8; 32];
+        if rng.percent(50) {
+            zero[rng.below(32)] = 1 << rng.below(8);
+        }
+        assert_eq!(fast_is_zero(&zero), zero == [0; 32]);
+
+        // 64-byte values: a key pair side by side, like a signature
+        let lhs: [u8; 64] = [a, keys[1]].concat().try_into().unwrap();
+        let rhs: [u8; 64] = [probe(rng, &keys), b].concat().try_into().unwrap();
+        assert_eq!(fast_eq_64(&lhs, &rhs), lhs == rhs);
+        let (lhs, rhs): (&[u8; 8], &[u8; 8]) =
+            (a[..8].try_into().unwrap(), b[..8].try_into().unwrap());
+        assert_eq!(fast_eq_8(lhs, rhs), lhs == rhs);
+        assert_eq!(fast_eq_n(lhs, rhs), lhs == rhs);
+        let (lhs, rhs): (&[u8; 16], &[u8; 16]) =
+            (a[16..].try_into().unwrap(), b[16..].try_into().unwrap());
+        assert_eq!(fast_eq_n(lhs, rhs), lhs == rhs);
+        assert_eq!(fast_eq_n(&a, &b), expected);
+        let lhs: [u8; 128] = [a; 4].concat().try_into().unwrap();
+        let rhs: [u8; 128] = [a, a, a, b].concat().try_into().unwrap();
+        assert_eq!(fast_eq_n(&lhs, &rhs), expected);
+
+        // Keys at odd offsets in packed data
+        let shift = rng.below(8);
+        let mut packed = vec![0u8; shift + 32];
+        packed[shift..].copy_from_slice(&a);
+        assert_eq!(fast_eq_unaligned(&packed[shift..], &b), expected);
+        assert!(!fast_eq_unaligned(&packed[shift + 1..], &b));
+        assert_eq!(
+            unsafe { fast_eq_raw(packed[shift..].as_ptr(), b.as_ptr()) },
+            expected
+        );
+    });
+}
+
+#[test]
+fn strided_scan_matches_reference() {
+    for_each_workload(|rng, profile| {
+        let n = rng.below(24);
+        let keys = gen_keys(rng, n, profile);
+        let stride = 32 + rng.below(24);
+        let start = rng.below(16);
+        let mut data = vec![0u8; start + n * stride + rng.below(40)];
+        rng.bytes(&mut data);
+        for (i, key) in keys.iter().enumerate() {
+            data[start + i * stride..][..32].copy_from_slice(key);
+        }
+
+        let target = probe(rng, &keys);
+        let expected = (0..)
+            .map(|i| start + i * stride)
+            .take_while(|offset| offset + 32 <= data.len())
+            .position(|offset| data[offset..offset + 32] == target);
+        assert_eq!(scan_keys(&data, start, stride, &target), expected);
+        assert_eq!(contains(&keys, &target), keys.contains(&target));
+        assert_eq!(any_eq(keys.iter().rev(), &target), keys.contains(&target));
+        let mut other = keys.clone();
+        if let Some(last) = other.last_mut() {
+            *last = target;
+        }
+        assert_eq!(slices_eq(&keys, &other), keys == other);
+        let provided: Vec<&Key> = other.iter().collect();
+        let mismatch = keys.iter().zip(&other).position(|(a, b)| a != b);
+        assert_eq!(
+            validate_keys(&keys, &provided),
+            mismatch.map_or(Ok(()), Err)
+        );
+        assert_eq!(
+            validate_keys(&keys, &provided[..n.saturating_sub(1)]),
+            mismatch.or(n.checked_sub(1)).map_or(Ok(()), Err)
+        );
+        assert_eq!(slices_eq(&keys, &keys[..n.saturating_sub(1)]), n == 0);
+        let shared = vec![target; n];
+        assert!(all_equal(&shared));
+        assert_eq!(all_equal(&other), other.iter().all(|key| *key == other[0]));
+        assert_eq!(
+            count_eq(&keys, &target),
+            keys.iter().filter(|key| **key == target).count()
+        );
+        assert_eq!(
+            position_of(&keys, &target),
+            keys.iter().position(|key| *key == target)
+        );
+        let signers: Vec<&Key> = other.iter().chain(&other).collect();
+        assert_eq!(
+            count_members(&signers, &keys),
+            keys.iter().filter(|key| other.contains(key)).count()
+        );
+
+        let pages: Vec<Vec<u8>> = keys
+            .chunks(1 + rng.below(6))
+            .map(|chunk| {
+                let mut page = vec![0u8; start];
+                for key in chunk {
+                    page.extend_from_slice(key);
+                    page.extend(std::iter::repeat_n(0, stride - 32));
+                }
+                page
+            })
+            .collect();
+        let pages: Vec<&[u8]> = pages.iter().map(Vec::as_slice).collect();
+        let mut search = MultiBufferSearch::new(&pages, start, stride);
+        let found: Vec<(usize, usize)> = std::iter::from_fn(|| search.find_next(&target)).collect();
+        let expected: Vec<(usize, usize)> = pages
+            .iter()
+            .enumerate()
+            .flat_map(|(page, data)| {
+                (0..(data.len() - start) / stride)
+                    .filter(|i| data[start + i * stride..][..32] == target)
+                    .map(move |i| (page, i))
+            })
+            .collect();
+        assert_eq!(found, expected);
+        search.reset();
+        assert_eq!(search.find_next(&target), expected.first().copied());
+
+        let mut packed = keys.concat();
+        if n > 0 && rng.below(2) == 0 {
+            packed[rng.below(n) * 32..][..32].fill(0);
+        }
+        packed.extend(std::iter::repeat_n(0, rng.below(32)));
+        assert_eq!(
+            find_zero_slot(&packed),
+            packed.chunks_exact(32).position(|entry| entry == [0u8; 32])
+        );
+
+        let offset = rng.below(data.len() + 8);
+        let expected = data.get(offset..offset + 32) == Some(&target[..]);
+        assert_eq!(fast_eq_at(&data, offset, &target), expected);
+    });
+}
+
+#[test]
+fn sorted_search_matches_reference() {
+    for_each_workload(|rng, profile| {
+        let n = rng.below(80);
+        let mut keys = gen_keys(rng, n, profile);
+        assert_eq!(is_sorted(&keys), keys.is_sorted());
+        let mut sorted = keys.clone();
+        sort_keys(&mut sorted);
+        if n > 0 {
+            assert_eq!(min_key(&keys), keys.iter().min().unwrap());
+            assert_eq!(max_key(&keys), keys.iter().max().unwrap());
+        }
+        let k = rng.below(n + 4);
+        let mut smallest = vec![[0u8; 32]; k];
+        let written = select_k_smallest(&keys, k, &mut smallest);
+        assert_eq!(written, k.min(n));
+        assert_eq!(&smallest[..written], &sorted[..written]);
+        let ordered: BTreeSet<FastOrd<Key>> = keys.iter().copied().map(FastOrd).collect();
+        let reference: BTreeSet<Key> = keys.iter().copied().collect();
+        assert!(ordered.into_iter().map(FastOrd::into_inner).eq(reference));
+        keys.sort_unstable();
+        assert_eq!(sorted, keys);
+        assert!(is_sorted(&keys));
+        let target = probe(rng, &keys);
+
+        match (
+            binary_search_keys(&keys, &target),
+            keys.binary_search(&target),
+        ) {
+            (Ok(index), Ok(_)) => assert_eq!(keys[index], target),
+            (found, reference) => assert_eq!(found, reference),
+        }
+    });
+}
+
+#[test]
+fn sorted_region_matches_reference() {
+    use solana_pubkey_compare::CompareError;
+
+    for_each_workload(|rng, profile| {
+        let capacity = rng.below(12);
+        let mut region = vec![0u8; capacity * 32 + rng.below(32)];
+        let mut count = 0u32;
+        let mut reference = BTreeSet::new();
+
+        let n = rng.below(16);
+        for key in gen_keys(rng, n, profile) {
+            let result = insert_sorted(&mut region, &mut count, &key);
+            if reference.contains(&key) {
+                assert!(matches!(result, Err(CompareError::DuplicateKey { .. })));
+            } else if reference.len() == capacity {
+                assert_eq!(result, Err(CompareError::RegionFull));
+            } else {
+                reference.insert(key);
+                assert_eq!(result, Ok(reference.range(..key).count()));
+            }
+            assert_eq!(count as usize, reference.len());
+        }
+        let stored: Vec<&[u8]> = region.chunks(32).take(count as usize).collect();
+        let expected: Vec<&[u8]> = reference.iter().map(|key| &key[..]).collect();
+        assert_eq!(stored, expected);
+
+        for _ in 0..rng.below(capacity + 2) {
+            let pool: Vec<Key> = reference.iter().copied().collect();
+            let key = probe(rng, &pool);
+            let result = remove_sorted(&mut region, &mut count, &key);
+            match reference.range(..key).count() {
+                index if reference.remove(&key) => assert_eq!(result, Ok(index)),
+                _ => assert_eq!(result, Err(CompareError::NotFound)),
+            }
+            assert_eq!(count as usize, reference.len());
+            assert!(
+                region[reference.len() * 32..capacity * 32]
+                    .iter()
+                    .all(|&byte| byte == 0)
+            );
+        }
+        let stored: Vec<&[u8]> = region.chunks(32).take(count as usize).collect();
+        let expected: Vec<&[u8]> = reference.iter().map(|key| &key[..]).collect();
+        assert_eq!(stored, expected);
+    });
+}
+
+#[test]
+fn pubkey_set_matches_reference() {
+    for_each_workload(|rng, profile| {
+        check_pubkey_set::<false>(rng, profile);
+        check_pubkey_set::<true>(rng, profile);
+    });
+}
+
+fn check_pubkey_set<const SORTED: bool>(rng: &mut Rng, profile: Profile) {
+    use solana_pubkey_compare::CompareError;
+
+    let pool = gen_keys(rng, 12, profile);
+    let mut data = vec![0u8; PubkeySet::<8, SORTED>::SIZE];
+    let set = PubkeySet::<8, SORTED>::from_bytes_mut(&mut data).unwrap();
+    let mut reference = BTreeSet::new();
+
+    for _ in 0..rng.below(32) {
+        let key = pool[rng.below(pool.len())];
+        if rng.below(3) == 0 {
+            assert_eq!(set.remove(&key), reference.remove(&key));
+        } else if reference.contains(&key) || reference.len() < 8 {
+            assert_eq!(set.insert(&key), Ok(reference.insert(key)));
+        } else {
+            assert_eq!(set.insert(&key), Err(CompareError::RegionFull));
+        }
+        let target = probe(rng, &pool);
+        assert_eq!(set.contains(&target), reference.contains(&target));
+    }
+
+    let mut stored = set.keys().to_vec();
+    if SORTED {
+        assert!(stored.is_sorted());
+    }
+    stored.sort_unstable();
+    assert!(stored.iter().eq(reference.iter()));
+    assert!(
+        data[8 + 32 * reference.len()..]
+            .iter()
+            .all(|&byte| byte == 0)
+    );
+}
+
+#[test]
+fn needle_search_matches_reference() {
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 4, profile);
+        let mut data = vec![0u8; rng.below(200)];
+        // Sparse alphabets make prefix-filter hits that fail verification
+        for b in &mut data {
+            *b = rng.below(2) as u8;
+        }
+        for key in &keys {
+            if data.len() >= 32 && rng.percent(50) {
+                let at = rng.below(data.len() - 31);
+                data[at..at + 32].copy_from_slice(key);
+            }
+        }
+
+        let target = probe(rng, &keys);
+        let expected = data.windows(32).position(|w| w == target);
+        assert_eq!(find_key_in_bytes(&data, &target), expected);
+    });
+}
+
+#[test]
+fn set_operations_match_reference() {
+    for_each_workload(|rng, profile| {
+        let pool = gen_keys(rng, 12, profile);
+        let pick = |rng: &mut Rng| -> Vec<Key> {
+            let n = rng.below(10);
+            (0..n).map(|_| pool[rng.below(pool.len())]).collect()
+        };
+        let (mut a, mut b) = (pick(rng), pick(rng));
+        let set_a: BTreeSet<Key> = a.iter().copied().collect();
+        let set_b: BTreeSet<Key> = b.iter().copied().collect();
+
+        assert_eq!(set_eq(&a, &b), set_a == set_b);
+        assert_eq!(all_unique(&a), set_a.len() == a.len());
+        assert_eq!(all_unique_in_place(&mut b.clone()), set_b.len() == b.len());
+        let mut deduped = a.clone();
+        let n = dedup_in_place(&mut deduped);
+        assert!(deduped[..n].iter().eq(set_a.iter()));
+        assert_eq!(is_subset_of_unsorted(&a, &b), set_a.is_subset(&set_b));
+
+        assert_eq!(set_eq_in_place(&mut a, &mut b), set_a == set_b);
+        assert_eq!(is_subset_of(&a, &b), set_a.is_subset(&set_b));
+        assert_eq!(is_superset_of(&a, &b), set_a.is_superset(&set_b));
+
+        let mut out = vec![[0u8; 32]; a.len() + b.len()];
+        let n = symmetric_difference(&a, &b, &mut out);
+        let expected: Vec<Key> = set_a.symmetric_difference(&set_b).copied().collect();
+        assert_eq!(&out[..n], &expected[..]);
+
+        let mut out = vec![[0u8; 32]; a.len().min(b.len())];
+        let n = intersect_sorted(&a, &b, &mut out);
+        let expected: Vec<Key> = set_a.intersection(&set_b).copied().collect();
+        assert_eq!(&out[..n], &expected[..]);
+
+        let mut out = vec![[0u8; 32]; a.len()];
+        let n = difference_sorted(&a, &b, &mut out);
+        let expected: Vec<Key> = set_a.difference(&set_b).copied().collect();
+        assert_eq!(&out[..n], &expected[..]);
+
+        let mut out = vec![[0u8; 32]; a.len() + b.len()];
+        let n = union_sorted(&a, &b, &mut out);
+        let expected: Vec<Key> = set_a.union(&set_b).copied().collect();
+        assert_eq!(&out[..n], &expected[..]);
+    });
+}
+
+#[test]
+fn lookup_table_and_parse_match_reference() {
+    for_each_workload(|rng, profile| {
+        let n = rng.below(8);
+        let keys = gen_keys(rng, n, profile);
+        let mut table = vec![0u8; alt::LOOKUP_TABLE_META_SIZE];
+        for key in &keys {
+            table.extend_from_slice(key);
+        }
+
+        let index = rng.below(10) as u8;
+        let target = probe(rng, &keys);
+        let expected = keys.get(index as usize) == Some(&target);
+        assert_eq!(alt::alt_key_eq(&table, index, &target), expected);
+        assert_eq!(alt::alt_key_at(&table, index), keys.get(index as usize));
+        let position = keys.iter().position(|key| *key == target);
+        assert_eq!(
+            alt::alt_index_of(&keys, &target),
+            position.map(|index| index as u8)
+        );
+        let mut long = vec![[0xffu8; 32]; alt::LOOKUP_TABLE_MAX_ADDRESSES];
+        long.extend(&keys);
+        long[rng.below(alt::LOOKUP_TABLE_MAX_ADDRESSES)] = target;
+        assert_eq!(
+            alt::alt_index_of(&long, &target),
+            long[..alt::LOOKUP_TABLE_MAX_ADDRESSES]
+                .iter()
+                .position(|key| *key == target)
+                .map(|index| index as u8)
+        );
+
+        let data = &table[alt::LOOKUP_TABLE_META_SIZE..];
+        let count = rng.below(keys.len() + 2);
+        match parse::keys_from_instruction_data(data, count) {
+            Ok(parsed) => assert_eq!(parsed, &keys[..count]),
+            Err(_) => assert!(count > keys.len()),
+        }
+    });
+}
+
+#[test]
+fn key_table_matches_reference() {
+    for_each_workload(|rng, profile| {
+        let n = rng.below(40);
+        let mut keys = gen_keys(rng, n, profile);
+        keys.sort_unstable();
+        let table = KeyTable::new(&keys);
+
+        let target = probe(rng, &keys);
+        let found = table.index_of(&target);
+        assert_eq!(
+            found.map(|i| keys[i]),
+            keys.contains(&target).then_some(target)
+        );
+        assert_eq!(table.contains(&target), keys.contains(&target));
+    });
+}
+
+#[cfg(all(feature = "std", feature = "solana-program"))]
+#[test]
+fn sanitize_metas_matches_reference() {
+    use solana_program::{instruction::AccountMeta, pubkey::Pubkey};
+    use solana_pubkey_compare::sanitize_metas;
+
+    for_each_workload(|rng, profile| {
+        let pool = gen_keys(rng, 8, profile);
+        let mut metas: Vec<AccountMeta> = (0..rng.below(16))
+            .map(|_| AccountMeta {
+                pubkey: Pubkey::new_from_array(pool[rng.below(pool.len())]),
+                is_signer: rng.below(2) == 0,
+                is_writable: rng.below(2) == 0,
+            })
+            .collect();
+
+        let mut expected: Vec<AccountMeta> = Vec::new();
+        for meta in &metas {
+            match expected.iter_mut().find(|seen| seen.pubkey == meta.pubkey) {
+                Some(seen) => {
+                    seen.is_signer |= meta.is_signer;
+                    seen.is_writable |= meta.is_writable;
+                }
+                None => expected.push(meta.clone()),
+            }
+        }
+        expected.sort_by_key(|meta| (!meta.is_signer, !meta.is_writable));
+
+        sanitize_metas(&mut metas);
+        assert_eq!(metas, expected);
+    });
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn fingerprint_is_stable() {
+    use std::collections::HashMap;
+
+    use solana_pubkey_compare::{FastHash, FastHashBuilder, fingerprint, fingerprint_seeded};
+
+    // Pinned: fingerprints may be persisted, so these values must not change
+    let key: Key = core::array::from_fn(|i| i as u8);
+    assert_eq!(fingerprint(&key), 0x72a8_1a00_97cc_9d1d);
+    assert_eq!(fingerprint_seeded(&key, 0xdead), 0x8c2e_22a6_b498_5887);
+    assert_eq!(fingerprint(&[0u8; 32]), 0);
+
+    // Every byte must reach the low bits that tables reduce to slots; 256
+    // keys spread over 1024 slots fill about 226 of them
+    for at in [0, 7, 24, 31] {
+        let slots: BTreeSet<u64> = (0..=255)
+            .map(|byte| {
+                let mut variant = key;
+                variant[at] = byte;
+                fingerprint(&variant) % 1024
+            })
+            .collect();
+        assert!(slots.len() > 192, "byte {at} reaches {} slots", slots.len());
+    }
+
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 2, profile);
+        let seed = rng.next();
+        assert_eq!(fingerprint(&keys[0]), fingerprint_seeded(&keys[0], 0));
+        assert_eq!(
+            fingerprint_seeded(&keys[0], seed) == fingerprint_seeded(&keys[1], seed),
+            keys[0] == keys[1]
+        );
+
+        let mut map: HashMap<FastHash<Key>, usize, FastHashBuilder> = HashMap::default();
+        let mut reference: HashMap<Key, usize> = HashMap::new();
+        let n = rng.below(32);
+        let entries = gen_keys(rng, n, profile);
+        for (i, key) in entries.iter().copied().enumerate() {
+            assert_eq!(map.insert(FastHash(key), i), reference.insert(key, i));
+        }
+        let target = probe(rng, &entries);
+        assert_eq!(map.get(FastHash::from_ref(&target)), reference.get(&target));
+    });
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn pubkey_map_matches_reference() {
+    use std::collections::HashMap;
+
+    use solana_pubkey_compare::{CompareError, PubkeyMap};
+
+    for_each_workload(|rng, profile| {
+        let pool = gen_keys(rng, 24, profile);
+        let mut map = PubkeyMap::<16, u64>::new();
+        let mut reference: HashMap<Key, u64> = HashMap::new();
+
+        for _ in 0..rng.below(64) {
+            let key = pool[rng.below(pool.len())];
+            if rng.below(3) == 0 {
+                assert_eq!(map.remove(&key), reference.remove(&key));
+            } else {
+                let value = rng.next();
+                match map.insert(&key, value) {
+                    Err(CompareError::RegionFull) => {
+                        assert!(!reference.contains_key(&key) && reference.len() == 16)
+                    }
+                    result => assert_eq!(result, Ok(reference.insert(key, value))),
+                }
+            }
+            assert_eq!(map.len(), reference.len());
+        }
+
+        for key in &pool {
+            assert_eq!(map.get(key), reference.get(key));
+        }
+        let mut stored: Vec<(Key, u64)> = map.iter().map(|(key, value)| (*key, *value)).collect();
+        let mut expected: Vec<(Key, u64)> = reference.into_iter().collect();
+        stored.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(stored, expected);
+    });
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn pubkey_bloom_has_no_false_negatives() {
+    use solana_pubkey_compare::PubkeyBloom;
+
+    let (mut false_positives, mut near_misses, mut near_miss_positives) = (0, 0, 0);
+    for_each_workload(|rng, profile| {
+        let n = rng.below(64);
+        let keys = gen_keys(rng, n, profile);
+        let mut data = vec![0u8; PubkeyBloom::<128>::SIZE];
+        let bloom = PubkeyBloom::<128>::from_bytes_mut(&mut data).unwrap();
+        for key in &keys {
+            bloom.insert(key);
+        }
+        assert!(keys.iter().all(|key| bloom.maybe_contains(key)));
+        assert!(bloom.count_ones() <= 4 * n);
+
+        let mut absent = [0u8; 32];
+        rng.bytes(&mut absent);
+        if !keys.contains(&absent) && bloom.maybe_contains(&absent) {
+            false_positives += 1;
+        }
+
+        // Absent keys sharing a prefix with an inserted key must not inherit
+        // its bits
+        if n > 0 && rng.percent(profile.prefix_share) {
+            let shared = [2, 8, 16, 24, 31][rng.below(5)];
+            absent[..shared].copy_from_slice(&keys[rng.below(n)][..shared]);
+            if !keys.contains(&absent) {
+                near_misses += 1;
+                if bloom.maybe_contains(&absent) {
+                    near_miss_positives += 1;
+                }
+            }
+        }
+        bloom.clear();
+        assert!(keys.iter().all(|key| !bloom.maybe_contains(key)));
+    });
+    // At most 64 keys in 1024 bits keeps the rate around 1% or below
+    assert!(false_positives < PROFILES.len() * ROUNDS / 20);
+    assert!(near_misses > 0 && near_miss_positives * 20 < near_misses);
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn interner_matches_reference() {
+    use std::collections::HashMap;
+
+    use solana_pubkey_compare::{Interner, KeyArena};
+
+    for_each_workload(|rng, profile| {
+        let n = rng.below(48);
+        let keys = gen_keys(rng, n, profile);
+        let mut interner = Interner::<32>::new();
+        let mut reference: HashMap<Key, u16> = HashMap::new();
+        for key in &keys {
+            let expected = match reference.get(key) {
+                Some(&id) => Some(id),
+                None if reference.len() < 32 => {
+                    let id = reference.len() as u16;
+                    reference.insert(*key, id);
+                    Some(id)
+                }
+                None => None,
+            };
+            assert_eq!(interner.intern(key), expected);
+        }
+        assert_eq!(interner.len(), reference.len());
+
+        let target = probe(rng, &keys);
+        assert_eq!(interner.id_of(&target), reference.get(&target).copied());
+        for (key, &id) in &reference {
+            assert_eq!(interner.key_of(id), Some(key));
+        }
+
+        let mut data = vec![0u8; KeyArena::<32>::SIZE];
+        let arena = KeyArena::<32>::from_bytes_mut(&mut data).unwrap();
+        interner.snapshot(arena);
+        assert_eq!(arena.len(), interner.len());
+        assert!(
+            arena
+                .iter()
+                .all(|(id, key)| interner.key_of(id) == Some(key))
+        );
+        let restored = Interner::<32>::restore(arena).unwrap();
+        assert_eq!(restored.id_of(&target), interner.id_of(&target));
+        for (key, &id) in &reference {
+            assert_eq!(restored.id_of(key), Some(id));
+        }
+    });
+}
+
+#[test]
+fn mint_authorities_match_reference() {
+    use solana_pubkey_compare::mint::{self, MINT_LEN};
+
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 2, profile);
+        let mut data = vec![0u8; MINT_LEN + rng.below(2) * 100];
+        rng.bytes(&mut data);
+        // Mostly valid tags, occasionally garbage
+        let tags = [rng.below(3) as u8, rng.below(3) as u8];
+        for (offset, (tag, key)) in [0, 46].into_iter().zip(tags.into_iter().zip(&keys)) {
+            data[offset..offset + 4].copy_from_slice(&[tag, 0, 0, 0]);
+            data[offset + 4..offset + 36].copy_from_slice(key);
+        }
+
+        let target = probe(rng, &keys);
+        assert_eq!(
+            mint::mint_authority_is(&data, &target),
+            tags[0] == 1 && keys[0] == target
+        );
+        assert_eq!(
+            mint::freeze_authority_is(&data, &target),
+            tags[1] == 1 && keys[1] == target
+        );
+        assert_eq!(mint::mint_authority(&data).is_err(), tags[0] > 1);
+        assert!(mint::mint_authority(&data[..MINT_LEN - 1]).is_err());
+        for (offset, (tag, key)) in [0, 46].into_iter().zip(tags.into_iter().zip(&keys)) {
+            let raw = data[offset..offset + 36].try_into().unwrap();
+            assert_eq!(
+                mint::coption_pubkey_eq(raw, &target),
+                tag == 1 && *key == target
+            );
+        }
+    });
+}
+
+#[test]
+fn token_delegate_matches_reference() {
+    use solana_pubkey_compare::token::{TOKEN_ACCOUNT_LEN, token_account_has_delegate};
+
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 1, profile);
+        let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+        rng.bytes(&mut data);
+        let tag = rng.below(3) as u8;
+        data[72..76].copy_from_slice(&[tag, 0, 0, 0]);
+        data[76..108].copy_from_slice(&keys[0]);
+
+        let target = probe(rng, &keys);
+        assert_eq!(
+            token_account_has_delegate(&data, Some(&target)),
+            tag == 1 && keys[0] == target
+        );
+        assert_eq!(token_account_has_delegate::<Key>(&data, None), tag == 0);
+    });
+}
+
+#[test]
+fn selection_matches_reference() {
+    for_each_workload(|rng, profile| {
+        let n = 1 + rng.below(40);
+        let mut keys = gen_keys(rng, n, profile);
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+
+        let k = rng.below(n);
+        assert_eq!(*select_kth(&mut keys, k), sorted[k]);
+        assert!(keys[..k].iter().all(|key| *key <= sorted[k]));
+        assert!(keys[k + 1..].iter().all(|key| *key >= sorted[k]));
+
+        let target = probe(rng, &keys);
+        let expected = keys.iter().filter(|key| **key == target).count();
+        let n = partition_eq(&mut keys, &target);
+        assert_eq!(n, expected);
+        assert!(keys[..n].iter().all(|key| *key == target));
+        assert!(keys[n..].iter().all(|key| *key != target));
+        keys.sort_unstable();
+        assert_eq!(keys, sorted);
+    });
+}
+
+#[cfg(feature = "copy")]
+#[test]
+fn key_cas_matches_reference() {
+    use solana_pubkey_compare::key_cas;
+
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 3, profile);
+        let mut slot = keys[0];
+        let expected = probe(rng, &keys);
+        let swapped = key_cas(&mut slot, &expected, &keys[2]);
+        assert_eq!(swapped, keys[0] == expected);
+        assert_eq!(slot, if swapped { keys[2] } else { keys[0] });
+    });
+}
+
+#[test]
+fn stake_pool_scan_matches_reference() {
+    use solana_pubkey_compare::stake_pool::{
+        VOTE_ACCOUNT_OFFSET, find_validator, validator_entry_offset,
+    };
+
+    for_each_workload(|rng, profile| {
+        let capacity = rng.below(12);
+        let count = rng.below(capacity + 1);
+        let votes = gen_keys(rng, capacity, profile);
+        let mut data = vec![0u8; validator_entry_offset(capacity)];
+        rng.bytes(&mut data);
+        data[5..9].copy_from_slice(&(count as u32).to_le_bytes());
+        for (i, vote) in votes.iter().enumerate() {
+            let at = validator_entry_offset(i) + VOTE_ACCOUNT_OFFSET;
+            data[at..at + 32].copy_from_slice(vote);
+        }
+
+        // Entries past `count` are stale and must never match
+        let target = probe(rng, &votes);
+        let expected = votes[..count].iter().position(|vote| *vote == target);
+        assert_eq!(find_validator(&data, &target).ok(), expected);
+    });
+}
+
+#[test]
+fn seeds_eq_matches_reference() {
+    use solana_pubkey_compare::seeds_eq;
+
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 4, profile);
+        let lhs: Vec<Vec<u8>> = keys
+            .iter()
+            .map(|key| key[..rng.below(33)].to_vec())
+            .collect();
+        let mut rhs = lhs.clone();
+        match rng.below(4) {
+            0 => {}
+            1 => {
+                let seed = &mut rhs[rng.below(4)];
+                if !seed.is_empty() {
+                    let at = rng.below(seed.len());
+                    seed[at] ^= 1 << rng.below(8);
+                }
+            }
+            2 => {
+                rhs[rng.below(4)].pop();
+            }
+            _ => {
+                rhs.pop();
+            }
+        }
+
+        let lhs: Vec<&[u8]> = lhs.iter().map(Vec::as_slice).collect();
+        let rhs: Vec<&[u8]> = rhs.iter().map(Vec::as_slice).collect();
+        assert_eq!(seeds_eq(&lhs, &rhs), lhs == rhs);
+    });
+}
+
+#[cfg(all(feature = "std", feature = "hash"))]
+#[test]
+fn key_set_digest_never_misses_overlap() {
+    use solana_pubkey_compare::KeySetDigest;
+
+    let mut false_overlaps = 0;
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 24, profile);
+        let a = &keys[..rng.below(13)];
+        let b = &keys[12 + rng.below(13)..];
+        let (da, db) = (KeySetDigest::from_keys(a), KeySetDigest::from_keys(b));
+
+        // Digests may report false overlaps but never miss a real one
+        if a.iter().any(|key| b.contains(key)) {
+            assert!(da.may_overlap(&db));
+        } else if da.may_overlap(&db) {
+            false_overlaps += 1;
+        }
+        for key in b {
+            assert!(db.may_contain(key));
+        }
+    });
+    // At most 144 key pairs per round at 2^-32 each: expect none
+    assert_eq!(false_overlaps, 0);
+
+    // Structured keys must spread like random ones
+    let repeated: Vec<Key> = (0..=255).map(|byte| [byte; 32]).collect();
+    assert_eq!(KeySetDigest::from_keys(&repeated).len(), repeated.len());
+    let counting: Vec<Key> = (0..256u32)
+        .map(|i| core::array::from_fn(|at| (i as usize + at) as u8))
+        .collect();
+    assert_eq!(KeySetDigest::from_keys(&counting).len(), counting.len());
+}
+
+#[test]
+fn locks_conflict_matches_reference() {
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 16, profile);
+        let mut lists: Vec<Vec<Key>> = (0..4)
+            .map(|_| (0..rng.below(5)).map(|_| keys[rng.below(16)]).collect())
+            .collect();
+        for list in &mut lists {
+            list.sort_unstable();
+        }
+        let [wa, ra, wb, rb] = [&lists[0], &lists[1], &lists[2], &lists[3]];
+
+        let shares = |x: &[Key], y: &[Key]| x.iter().any(|key| y.contains(key));
+        let expected = shares(wa, wb) || shares(wa, rb) || shares(ra, wb);
+        assert_eq!(locks_conflict(wa, ra, wb, rb), expected);
+    });
+}
+
+#[test]
+#[should_panic(expected = "fast_assert_eq failed")]
+fn fast_assert_eq_panics_on_mismatch() {
+    let mut key = [5u8; 32];
+    key[17] ^= 1;
+    fast_assert_eq(&key, &[5u8; 32]);
+}
+
+#[test]
+#[should_panic(expected = "code 6001")]
+fn fast_assert_eq_with_code_reports_code() {
+    let mut key = [5u8; 32];
+    key[30] ^= 1;
+    fast_assert_eq_with_code(&key, &[5u8; 32], 6001);
+}
+
+#[test]
+fn aligned_keys_match_reference() {
+    use solana_pubkey_compare::{AlignedPubkey, fast_eq_aligned};
+
+    for_each_workload(|rng, profile| {
+        let keys = gen_keys(rng, 2, profile);
+        let (a, b) = (keys[0], probe(rng, &keys));
+        let (x, y) = (AlignedPubkey::new(a), AlignedPubkey::from_key(&b));
+        assert_eq!(fast_eq_aligned(&x, &y), a == b);
+        assert_eq!(x == y, a == b);
+        assert_eq!(fast_eq(&x, &y), a == b);
+        assert_eq!(fast_eq2(&x, &b), a == b);
+        assert_eq!(fast_eq2(&b, x.as_words()), a == b);
+    });
+}